@@ -25,7 +25,8 @@
 // ============================================================================
 
 use std::cmp::Ordering;
-use rayon::prelude::*;
+
+use crate::primitives::filter;
 
 type Idx = usize;
 
@@ -145,12 +146,7 @@ impl<'a, H: HashHelper> HashTable<H>
     }
 
     pub fn entries(&self) -> Vec<H::ET> {
-        // FIXME:JA: replace with our own filter after implementing that
-        self.ta
-            .par_iter()
-            .cloned()
-            .filter(|v| !H::is_empty(v))
-            .collect()
+        filter(&self.ta, |v| !H::is_empty(v))
     }
 
     pub fn _find_index(&self, _v: H::KT) -> Idx {
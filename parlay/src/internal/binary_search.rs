@@ -53,3 +53,22 @@ where
 
     start + linear_search(&inp[start..end], p, less)
 }
+
+/// Same as `binary_search`, but compares the elements by reference.
+pub fn binary_search_by<T, F>(inp: &[T], p: &T, less: F) -> usize
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let (mut start, mut end) = (0, inp.len());
+
+    while end - start > BIN_SEARCH_BASE {
+        let mid = (start + end) / 2;
+        if !less(&inp[mid], p) { end = mid; }
+        else { start = mid + 1; }
+    }
+
+    start + inp[start..end]
+        .iter()
+        .position(|x| !less(x, p))
+        .unwrap_or(end - start)
+}
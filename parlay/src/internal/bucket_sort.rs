@@ -24,17 +24,23 @@
 // SOFTWARE.
 // ============================================================================
 
+use std::ptr;
+use std::mem::MaybeUninit;
 use rayon::prelude::*;
 
 use enhanced_rayon::prelude::*;
 use crate::maybe_uninit_vec;
-use crate::internal::{quick_sort, merge_sort_};
-use crate::utilities::hash64;
+use crate::internal::quick_sort::quick_sort_by;
+use crate::internal::merge_sort::merge_sort_by_;
+use crate::utilities::{
+    hash64, AbortOnPanic, uninit_buffer, as_uninit_mut, assume_init_mut
+};
 
 
-fn radix_step<T: Copy>(
+/// moves (bitwise copies) the elements of `inp` to `out`
+unsafe fn radix_step<T>(
     inp: &[T],
-    out: &mut [T],
+    out: &mut [MaybeUninit<T>],
     keys: &[u8],
     counts: &mut [usize]
 ) {
@@ -42,13 +48,15 @@ fn radix_step<T: Copy>(
     keys.iter().for_each(|&k| counts[k as usize] += 1);
     let mut s = 0;
     counts.iter_mut().for_each(|c| { s += *c; *c = s; });
+    // buckets are filled from the back, so go backwards to keep it stable
     keys
         .iter()
         .zip(inp.iter())
-        .for_each(|(&k, &v)| {
+        .rev()
+        .for_each(|(&k, v)| {
             let c = &mut counts[k as usize];
             *c -= 1;
-            out[*c] = v;
+            out[*c].write(ptr::read(v));
         });
 }
 
@@ -82,11 +90,11 @@ fn to_balanced_tree<T: Copy>(
 fn get_buckets<T, F>(
     inp: &[T],
     buckets: &mut [u8],
-    less: F,
+    less: &F,
     rounds: usize
 ) -> bool where
-    T: Copy + Sync,
-    F: Fn(T, T) -> bool + Copy + Sync,
+    T: Sync,
+    F: Fn(&T, &T) -> bool + Sync,
 {
     let n = inp.len();
     let num_buckets = 1 << rounds;
@@ -100,12 +108,12 @@ fn get_buckets<T, F>(
         .collect();
 
     // sort the samples
-    quick_sort(&mut sample_set, |a, b| less(inp[a], inp[b]));
+    quick_sort_by(&mut sample_set, &|a: &usize, b: &usize| less(&inp[*a], &inp[*b]));
     
     let pivots: Vec<_> = (0..num_pivots)
         .map(|i| sample_set[over_sample * (i+1)])
         .collect();
-    if !less(inp[pivots[0]], inp[pivots[num_pivots-1]]) { return true; }
+    if !less(&inp[pivots[0]], &inp[pivots[num_pivots-1]]) { return true; }
 
     let pivot_tree = &mut sample_set;
     to_balanced_tree(&pivots, pivot_tree, 0, 0, num_pivots);
@@ -114,7 +122,7 @@ fn get_buckets<T, F>(
     for i in 0..n {
         let mut j = 0;
         for _ in 0..rounds {
-            j = 1 + 2 * j + (!less(inp[i], inp[pivot_tree[j]])) as usize;
+            j = 1 + 2 * j + (!less(&inp[i], &inp[pivot_tree[j]])) as usize;
         }
         debug_assert!(j - num_pivots <= u8::MAX as usize);
         buckets[i] = (j - num_pivots) as u8;
@@ -122,33 +130,38 @@ fn get_buckets<T, F>(
     false
 }
 
-fn base_sort<T, F>(
+unsafe fn base_sort<T, F>(
     inp: &mut [T],
-    out: &mut [T],
-    less: F,
+    out: &mut [MaybeUninit<T>],
+    less: &F,
     stable: bool,
     inplace: bool
 ) where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
 {
     if stable {
-        merge_sort_(inp, out, less, inplace);
+        merge_sort_by_(inp, out, less, inplace);
     } else {
-        quick_sort(inp, less);
-        if !inplace { out.copy_from_slice(inp); }
+        quick_sort_by(inp, less);
+        if !inplace {
+            ptr::copy_nonoverlapping(inp.as_ptr(), out.as_mut_ptr() as *mut T, inp.len());
+        }
     }
 }
 
-pub fn bucket_sort_r<T, F>(
+/// Sorts `inp` leaving the result in `inp` if `inplace` and in `out`
+/// otherwise; `out` starts uninitialized, and whichever of the two does not
+/// hold the result must then be treated as uninitialized.
+pub(crate) unsafe fn bucket_sort_r<T, F>(
     inp: &mut [T],
-    out: &mut [T],
-    less: F,
+    out: &mut [MaybeUninit<T>],
+    less: &F,
     stable: bool,
     inplace: bool
 ) where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
 {
     let n = inp.len();
     const BITS: usize = 4;
@@ -162,6 +175,8 @@ pub fn bucket_sort_r<T, F>(
             base_sort(inp, out, less, stable, inplace);
         } else {
             radix_step(inp, out, &mut buckets, &mut counts);
+            // the elements now live in `out`, and `inp` is the scratch space
+            let (out, inp) = (assume_init_mut(out), as_uninit_mut(inp));
             out
                 .par_ind_chunks_mut(&counts)
                 .zip(inp.par_ind_chunks_mut(&counts))
@@ -180,5 +195,20 @@ where
     if inp.len() < 2 { return; }
     let n = inp.len();
     let mut tmp = maybe_uninit_vec![inp[0]; n];
-    bucket_sort_r(inp, &mut tmp, less, stable, true);
+    let less = |a: &T, b: &T| less(*a, *b);
+    unsafe { bucket_sort_r(inp, as_uninit_mut(&mut tmp), &less, stable, true); }
+}
+
+/// Parallel bucket sort of non-`Copy` elements.
+pub fn nc_bucket_sort<T, F>(inp: &mut [T], less: F, stable: bool)
+where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    if inp.len() < 2 { return; }
+    let n = inp.len();
+    let _guard = AbortOnPanic;
+    // dropping the buffer never drops elements
+    let mut tmp = uninit_buffer(n);
+    unsafe { bucket_sort_r(inp, &mut tmp, &less, stable, true); }
 }
@@ -35,8 +35,8 @@ use crate::internal::counting_sort::count_sort;
 use crate::internal::integer_sort::integer_sort_;
 
 const CR_SEQ_THR: usize = 8192;
-const CRS_SEQ_THR: usize = 10000;
-const CACHE_PER_THREAD: usize = 1000000;
+pub(crate) const CRS_SEQ_THR: usize = 10000;
+pub(crate) const CACHE_PER_THREAD: usize = 1000000;
 
 
 pub trait HashEq {
@@ -55,12 +55,12 @@ pub trait HashEq {
     fn equal(&self, _a: Self::KT, _b: Self::KT) -> bool { todo!() }
 }
 
-struct GetBucket<KT: Copy + Default, HEQ>
+pub(crate) struct GetBucket<KT: Copy + Default, HEQ>
 {
     hash_table: Vec<(KT, i32)>,
     table_mask: usize,
     bucket_mask: usize,
-    heavy_hitters: usize,
+    pub(crate) heavy_hitters: usize,
     heq: HEQ,
 }
 
@@ -70,7 +70,7 @@ where
     KT: Copy + Default + Send,
     HEQ: HashEq<IT=IT, KT=KT>,
 {
-    fn new(inp: &[IT], bits: usize, heq: HEQ) -> Self {
+    pub(crate) fn new(inp: &[IT], bits: usize, heq: HEQ) -> Self {
         let n = inp.len();
         let num_buckets = 1 << bits;
         const COPY_CUTOFF: usize = 5;
@@ -122,7 +122,7 @@ where
         Self { hash_table, table_mask, bucket_mask, heavy_hitters, heq }
     }
 
-    fn op(&self, v: &IT) -> usize {
+    pub(crate) fn op(&self, v: &IT) -> usize {
         let mut hash_val = self.heq.hash(self.heq.get_key(*v));
         if self.heavy_hitters > 0 {
             let h = &self.hash_table[hash_val & self.table_mask];
@@ -143,21 +143,23 @@ where
 pub trait RCHashEq {
     type IT;
     type KT;
+    type RT;
 
-    fn init(&self) -> Self::IT;
-    fn get_val(&self, a: Self::IT) -> Self::IT;
-    fn update(&self, r: &mut Self::IT, inp: Self::IT);
-    fn combine(&self, r: &mut Self::IT, inp: &[Self::IT]);
+    fn init(&self) -> Self::RT;
+    fn get_val(&self, a: Self::IT) -> Self::RT;
+    fn update(&self, r: &mut Self::RT, inp: Self::RT);
+    fn combine(&self, r: &mut Self::RT, inp: &[Self::IT]);
 }
 
-pub fn seq_collect_reduce<T, HEQ>(
+pub fn seq_collect_reduce<T, R, HEQ>(
     inp: &[T],
     helper: HEQ,
     num_buckets: usize,
-    res: &mut Vec<T>
+    res: &mut Vec<R>
 ) where
-    T: PrimInt + Default,
-    HEQ: RCHashEq<IT=T> + HashEq<IT=T> + Copy + Clone,
+    T: Copy,
+    R: Clone,
+    HEQ: RCHashEq<IT=T, RT=R> + HashEq<IT=T> + Copy + Clone,
     <HEQ as HashEq>::KT: PrimInt + Default,
     <HEQ as RCHashEq>::KT: PrimInt + Default,
 {
@@ -169,14 +171,15 @@ pub fn seq_collect_reduce<T, HEQ>(
     }
 }
 
-pub fn collect_reduce_few<T, HEQ>(
+pub fn collect_reduce_few<T, R, HEQ>(
     inp: &[T],
     helper: HEQ,
     num_buckets: usize,
-    res: &mut Vec<T>
+    res: &mut Vec<R>
 ) where
-    T: PrimInt + Send + Sync + Default,
-    HEQ: RCHashEq<IT=T> + HashEq<IT=T> + Send + Sync + Copy + Clone,
+    T: Copy + Send + Sync,
+    R: Copy + Send + Sync,
+    HEQ: RCHashEq<IT=T, RT=R> + HashEq<IT=T> + Send + Sync + Copy + Clone,
     <HEQ as HashEq>::KT: PrimInt + Default + Send + Sync,
     <HEQ as RCHashEq>::KT: PrimInt + Default + Send + Sync,
 {
@@ -192,7 +195,7 @@ pub fn collect_reduce_few<T, HEQ>(
 
     // partial results for each block
     let block_size = ((n - 1) / num_blocks) + 1;
-    let mut out: Vec<Vec<T>> = maybe_uninit_vec![vec![]; num_blocks];
+    let mut out: Vec<Vec<R>> = vec![vec![]; num_blocks];
     out
         .par_iter_mut()
         .enumerate()
@@ -214,14 +217,15 @@ pub fn collect_reduce_few<T, HEQ>(
         }).collect();
 }
 
-pub fn collect_reduce<T, HEQ>(
+pub fn collect_reduce<T, R, HEQ>(
     inp: &[T],
     helper: HEQ,
     num_buckets: usize,
-    res: &mut Vec<T>
+    res: &mut Vec<R>
 ) where
-    T: PrimInt + Send + Sync + Default,
-    HEQ: RCHashEq<IT=T> + HashEq<IT=T> + Send + Sync + Copy + Clone,
+    T: Copy + Send + Sync + Default,
+    R: Copy + Send + Sync,
+    HEQ: RCHashEq<IT=T, RT=R> + HashEq<IT=T> + Send + Sync + Copy + Clone,
     <HEQ as HashEq>::KT: PrimInt + Default + Send + Sync,
     <HEQ as RCHashEq>::KT: PrimInt + Default + Send + Sync,
 {
@@ -304,7 +308,7 @@ pub fn collect_reduce<T, HEQ>(
             if i < gb.heavy_hitters {   // heavy hitters with all equal keys
                 let k = helper.get_key(slice[0]).to_usize().unwrap();
                 helper.combine(
-                    unsafe { &mut *(sums.as_ptr().add(k) as *mut T) },
+                    unsafe { &mut *(sums.as_ptr().add(k) as *mut R) },
                     slice
                 );
             } else {    // shared blocks
@@ -312,7 +316,7 @@ pub fn collect_reduce<T, HEQ>(
                     let k = helper.get_key(slice[j]).to_usize().unwrap();
                     debug_assert!(k < num_buckets);
                     helper.update(
-                        unsafe { &mut *(sums.as_ptr().add(k) as *mut T) },
+                        unsafe { &mut *(sums.as_ptr().add(k) as *mut R) },
                         helper.get_val(slice[j])
                     );
                 }
@@ -389,7 +393,7 @@ pub fn collect_reduce_sparse<T, R, HEQ>(
     helper: HEQ,
     res: &mut Vec<R>
 ) where
-    T: Copy + Eq + Send + Sync + Default,
+    T: Copy + Send + Sync + Default,
     R: Copy + Send + Sync + Default,
     HEQ: RCSHashEq<IT=T, RT=R> + HashEq<IT=T, RT=R> + Send + Sync + Copy + Clone,
     <HEQ as HashEq>::KT: Copy + Default + Send + Sync,
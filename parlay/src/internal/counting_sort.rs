@@ -87,9 +87,11 @@ fn seq_write_down_<T: Copy, F: ToPrimitive>(
     keys: &[F],
     offsets: &mut [DefInt]
 ) {
+    // buckets are filled from the back, so go backwards to keep it stable
     inp
         .iter()
         .zip(keys.iter())
+        .rev()
         .for_each(|(i, k)| {
             let k = k.to_usize().unwrap();
             offsets[k] -= 1;
@@ -24,11 +24,19 @@
 // SOFTWARE.
 // ============================================================================
 
-use num_traits::PrimInt;
+use num_traits::{PrimInt, Zero};
+use rayon::prelude::*;
+use std::mem::size_of;
 use std::marker::PhantomData;
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+use enhanced_rayon::prelude::*;
 
-use crate::utilities::hash64_cheap;
+use crate::primitives::pack_index;
+use crate::utilities::{hash64_cheap, log2_up};
 use super::collect_reduce::*;
+use super::binary_search::binary_search;
+use super::counting_sort::{count_sort, seq_count_sort};
 
 
 #[derive(Clone, Copy)]
@@ -66,6 +74,60 @@ where
     collect_reduce_sparse(inp, helper, res);
 }
 
+// works on (hash, reference) pairs, so that `T` does not need to be `Copy`;
+// the reference is only `None` for the default entries of the scratch space
+struct HashedDedupHelper<'a, T> { _p: PhantomData<&'a T> }
+
+impl<T> Clone for HashedDedupHelper<'_, T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for HashedDedupHelper<'_, T> {}
+
+impl<'a, T: Eq> HashEq for HashedDedupHelper<'a, T>
+{
+    type IT = (u64, Option<&'a T>);
+    type KT = (u64, Option<&'a T>);
+    type RT = (u64, Option<&'a T>);
+
+    fn hash(&self, a: Self::KT) -> usize { a.0 as usize }
+    fn get_key(&self, a: Self::IT) -> Self::KT { a }
+    fn get_key_mut<'b>(&'b self, a: &'b mut Self::IT) -> &'b mut Self::KT { a }
+    fn get_key_from_result(&self, a: Self::RT) -> Self::KT { a }
+    fn equal(&self, a: Self::KT, b: Self::KT) -> bool { a == b }
+}
+
+impl<'a, T: Eq> RCSHashEq for HashedDedupHelper<'a, T>
+{
+    type IT = (u64, Option<&'a T>);
+    type KT = (u64, Option<&'a T>);
+    type RT = (u64, Option<&'a T>);
+
+    fn init(&self, _r: &mut Self::RT, _inp: Self::IT) {}
+    fn reduce(&self, s: &[Self::IT]) -> Self::RT { s[0] }
+    fn update(&self, _r: &mut Self::RT, _inp: Self::IT) {}
+}
+
+/// Removes the duplicates of `inp` for any hashable `T`. The order of the
+/// result is unspecified. Prefer `remove_duplicates` for primitive integers.
+pub fn remove_duplicates_hashed<T>(inp: &[T], res: &mut Vec<T>)
+where
+    T: Hash + Eq + Clone + Send + Sync,
+{
+    let hashed: Vec<(u64, Option<&T>)> = inp
+        .par_iter()
+        .map(|a| {
+            let mut h = DefaultHasher::new();
+            a.hash(&mut h);
+            (h.finish(), Some(a))
+        }).collect();
+
+    let helper = HashedDedupHelper { _p: PhantomData };
+    let mut r = vec![];
+    collect_reduce_sparse(&hashed, helper, &mut r);
+    *res = r.into_par_iter().map(|(_, a)| a.unwrap().clone()).collect();
+}
+
 #[derive(Clone, Copy)]
 struct HistHelper<T, K> {
     _t: PhantomData<T>,
@@ -99,6 +161,7 @@ impl<T: PrimInt + Default, K> RCHashEq for HistHelper<T, K>
 {
     type IT = T;
     type KT = K;
+    type RT = T;
 
     fn init(&self) -> Self::IT { T::zero() }
     fn get_val(&self, _a: Self::IT) -> Self::IT { T::one() }
@@ -124,6 +187,88 @@ pub fn histogram_by_index<T, K>(
     collect_reduce(inp, helper, num_buckets, res);
 }
 
+#[derive(Clone, Copy)]
+struct WeightedHistHelper<K, W> {
+    _k: PhantomData<K>,
+    _w: PhantomData<W>
+}
+
+impl<K, W> WeightedHistHelper<K, W> {
+    const SHIFT: usize = 8 / std::mem::size_of::<K>();
+
+    fn new() -> Self {
+        Self { _k: PhantomData, _w: PhantomData }
+    }
+}
+
+impl<K: PrimInt, W> HashEq for WeightedHistHelper<K, W>
+{
+    type IT = (K, W);
+    type KT = K;
+    type RT = W;
+
+    fn hash(&self, a: Self::KT) -> usize {
+        let v = ((a.to_usize().unwrap() + Self::SHIFT) & !15) as u64;
+        hash64_cheap(v) as usize
+    }
+
+    fn get_key(&self, a: Self::IT) -> Self::KT { a.0 }
+    fn equal(&self, a: Self::KT, b: Self::KT) -> bool { a.eq(&b) }
+}
+
+impl<K, W: Zero + Copy> RCHashEq for WeightedHistHelper<K, W>
+{
+    type IT = (K, W);
+    type KT = K;
+    type RT = W;
+
+    fn init(&self) -> Self::RT { W::zero() }
+    fn get_val(&self, a: Self::IT) -> Self::RT { a.1 }
+
+    fn update(&self, r: &mut Self::RT, inp: Self::RT) {
+        *r = *r + inp;
+    }
+
+    fn combine(&self, r: &mut Self::RT, inp: &[Self::IT]) {
+        *r = inp.iter().fold(*r, |s, a| s + a.1);
+    }
+}
+
+/// Sums the weights of `inp` by their keys, which must be less than
+/// `num_buckets`.
+pub fn weighted_histogram_by_index<K, W>(
+    inp: &[(K, W)],
+    num_buckets: usize,
+    res: &mut Vec<W>
+) where
+    K: PrimInt + Default + Send + Sync,
+    W: Zero + Copy + Default + Send + Sync,
+{
+    let helper = WeightedHistHelper::<K, W>::new();
+    collect_reduce(inp, helper, num_buckets, res);
+}
+
+/// Counts the values of `inp` that fall in each bucket
+/// `[bounds[i], bounds[i+1])`. `bounds` must be sorted; values outside
+/// of `[bounds[0], bounds[last])` (and NaNs) are not counted.
+pub fn histogram_by_bounds<T, C>(inp: &[T], bounds: &[T], res: &mut Vec<C>)
+where
+    T: PartialOrd + Copy + Send + Sync,
+    C: PrimInt + Default + Send + Sync,
+{
+    let num_buckets = bounds.len().saturating_sub(1);
+    let idx: Vec<C> = inp
+        .par_iter()
+        .filter_map(|&x| {
+            match binary_search(bounds, x, |a, b| a <= b) {
+                0 => None,
+                i if i > num_buckets => None,
+                i => Some(C::from(i - 1).unwrap()),
+            }
+        }).collect();
+    histogram_by_index::<C, u32>(&idx, num_buckets, res);
+}
+
 
 
 #[derive(Clone, Copy)]
@@ -194,3 +339,285 @@ where
     let helper = CountByKeyHelper::<T, S, F>::new(hash);
     collect_reduce_sparse(inp, helper, res);
 }
+
+
+
+#[derive(Clone, Copy)]
+struct SemisortHelper<T, K, G, F> {
+    _t: PhantomData<T>,
+    _k: PhantomData<K>,
+    get_key: G,
+    hash_fn: F
+}
+
+impl<T, K, G, F> HashEq for SemisortHelper<T, K, G, F>
+where
+    K: Eq + Copy,
+    G: Fn(T) -> K + Send + Sync + Copy + Clone,
+    F: Fn(K) -> usize + Send + Sync + Copy + Clone
+{
+    type IT = T;
+    type KT = K;
+    type RT = T;
+
+    fn hash(&self, a: Self::KT) -> usize { (self.hash_fn)(a) }
+    fn get_key(&self, a: Self::IT) -> Self::KT { (self.get_key)(a) }
+    fn equal(&self, a: Self::KT, b: Self::KT) -> bool { a.eq(&b) }
+}
+
+fn seq_semisort<T, K, G, F>(arr: &mut [T], get_key: G, hash: F)
+where
+    T: Copy,
+    K: Eq + Copy,
+    G: Fn(T) -> K,
+    F: Fn(K) -> usize,
+{
+    let n = arr.len();
+    if n <= 1 { return; }
+
+    // number the distinct keys in order of first appearance
+    let table_size = 3 * n / 2;
+    let mut table: Vec<Option<(K, usize)>> = vec![None; table_size];
+    let mut ids = vec![0usize; n];
+    let mut count = 0usize;
+    for j in 0..n {
+        let key = get_key(arr[j]);
+        let mut k = hash(key) % table_size;
+        loop {
+            match table[k] {
+                Some((kk, id)) if kk == key => { ids[j] = id; break; },
+                Some(_) => { k = if k + 1 == table_size { 0 } else { k + 1 }; },
+                None => {
+                    table[k] = Some((key, count));
+                    ids[j] = count;
+                    count += 1;
+                    break;
+                }
+            }
+        }
+    }
+
+    let tmp = arr.to_vec();
+    seq_count_sort(&tmp, arr, &ids, count);
+}
+
+/// Reorders `arr` so that elements with equal keys are contiguous, in
+/// expected linear work. The order of the groups is unspecified.
+pub fn semisort_inplace<T, K, G, F>(arr: &mut [T], get_key: G, hash: F)
+where
+    T: Copy + Send + Sync,
+    K: Eq + Copy + Default + Send + Sync,
+    G: Fn(T) -> K + Send + Sync + Copy + Clone,
+    F: Fn(K) -> usize + Send + Sync + Copy + Clone
+{
+    let n = arr.len();
+    if n < CRS_SEQ_THR {
+        seq_semisort(arr, get_key, hash);
+        return;
+    }
+
+    let bits = log2_up(
+        (1.0 + (1.2 * 2.0 * size_of::<T>() as f64 * n as f64)
+        / CACHE_PER_THREAD as f64) as usize
+    ).max(4);
+    let num_buckets = 1 << bits;
+
+    // heavy keys get a bucket of their own, the rest are spread by hash
+    let helper = SemisortHelper {
+        _t: PhantomData, _k: PhantomData, get_key, hash_fn: hash
+    };
+    let gb = GetBucket::new(arr, bits, helper);
+    let keys: Vec<usize> = arr.par_iter().map(|a| gb.op(a)).collect();
+    let tmp = arr.to_vec();
+    let (bucket_offsets, _) = count_sort(&tmp, arr, &keys, num_buckets, 1.0);
+
+    let heavy_cutoff = gb.heavy_hitters;
+    arr
+        .par_ind_chunks_mut(&bucket_offsets[..num_buckets])
+        .enumerate()
+        .for_each(|(i, block)| {
+            if i >= heavy_cutoff { seq_semisort(block, get_key, hash); }
+        });
+}
+
+/// Groups the values of `inp` by key. Each key appears once in the result,
+/// with its values in their original order; the order of the keys is
+/// unspecified.
+pub fn group_by_key<K, V, F>(inp: &[(K, V)], hash: F) -> Vec<(K, Vec<V>)>
+where
+    K: Eq + Copy + Default + Send + Sync,
+    V: Copy + Send + Sync,
+    F: Fn(K) -> usize + Send + Sync + Copy + Clone
+{
+    let n = inp.len();
+    if n == 0 { return vec![]; }
+
+    let mut a = inp.to_vec();
+    semisort_inplace(&mut a, |(k, _)| k, hash);
+
+    let flags: Vec<bool> = (0..n)
+        .into_par_iter()
+        .map(|i| i == 0 || a[i].0 != a[i-1].0)
+        .collect();
+    let mut starts: Vec<usize> = vec![];
+    pack_index(&flags, &mut starts);
+
+    let m = starts.len();
+    (0..m)
+        .into_par_iter()
+        .map(|i| {
+            let e = if i + 1 == m { n } else { starts[i+1] };
+            let group = &a[starts[i]..e];
+            (group[0].0, group.iter().map(|(_, v)| *v).collect())
+        }).collect()
+}
+
+
+
+#[derive(Clone, Copy)]
+struct ReduceHelper<T, V, K, G, H, F> {
+    _p: PhantomData<(T, K)>,
+    get_key: G,
+    get_value: H,
+    identity: V,
+    combine: F
+}
+
+impl<T, V, K, G, H, F> HashEq for ReduceHelper<T, V, K, G, H, F>
+where
+    T: Copy,
+    V: Copy,
+    K: PrimInt,
+    G: Fn(T) -> K + Send + Sync + Copy + Clone,
+    H: Fn(T) -> V + Send + Sync + Copy + Clone,
+    F: Fn(V, V) -> V + Send + Sync + Copy + Clone
+{
+    type IT = T;
+    type KT = K;
+    type RT = (K, V);
+
+    fn hash(&self, a: Self::KT) -> usize {
+        let v = a.to_u64().unwrap_or_else(|| a.to_i64().unwrap() as u64);
+        hash64_cheap(v) as usize
+    }
+
+    fn get_key(&self, a: Self::IT) -> Self::KT { (self.get_key)(a) }
+
+    fn get_key_mut<'a>(&'a self, a: &'a mut Self::RT) -> &'a mut Self::KT {
+        &mut a.0
+    }
+
+    fn get_key_from_result(&self, a: Self::RT) -> Self::KT { a.0 }
+    fn equal(&self, a: Self::KT, b: Self::KT) -> bool { a.eq(&b) }
+}
+
+// dense: one (possibly empty) result per index
+impl<T, V, K, G, H, F> RCHashEq for ReduceHelper<T, V, K, G, H, F>
+where
+    T: Copy,
+    V: Copy,
+    H: Fn(T) -> V,
+    F: Fn(V, V) -> V
+{
+    type IT = T;
+    type KT = K;
+    type RT = Option<V>;
+
+    fn init(&self) -> Self::RT { None }
+    fn get_val(&self, a: Self::IT) -> Self::RT { Some((self.get_value)(a)) }
+
+    fn update(&self, r: &mut Self::RT, inp: Self::RT) {
+        if let Some(v) = inp {
+            *r = Some((self.combine)(r.unwrap_or(self.identity), v));
+        }
+    }
+
+    fn combine(&self, r: &mut Self::RT, inp: &[Self::IT]) {
+        let s = r.unwrap_or(self.identity);
+        *r = Some(inp
+            .iter()
+            .fold(s, |a, &b| (self.combine)(a, (self.get_value)(b))));
+    }
+}
+
+// sparse: one result per distinct key
+impl<T, V, K, G, H, F> RCSHashEq for ReduceHelper<T, V, K, G, H, F>
+where
+    T: Copy,
+    V: Copy,
+    G: Fn(T) -> K,
+    H: Fn(T) -> V,
+    F: Fn(V, V) -> V
+{
+    type IT = T;
+    type KT = K;
+    type RT = (K, V);
+
+    fn init(&self, r: &mut Self::RT, inp: Self::IT) {
+        r.1 = (self.combine)(self.identity, (self.get_value)(inp));
+    }
+
+    fn reduce(&self, s: &[Self::IT]) -> Self::RT {
+        let v = s
+            .iter()
+            .fold(self.identity, |a, &b| (self.combine)(a, (self.get_value)(b)));
+        ((self.get_key)(s[0]), v)
+    }
+
+    fn update(&self, r: &mut Self::RT, inp: Self::IT) {
+        r.1 = (self.combine)(r.1, (self.get_value)(inp));
+    }
+}
+
+/// Reduces the values (`get_value`) of the elements of `inp` that share a
+/// key (`get_key`) with `combine`, which must be associative and commutative
+/// and have `identity` as its identity. Returns one `(key, reduction)` pair
+/// per distinct key, in no particular order.
+///
+/// Small non-negative keys (less than `inp.len()`) are reduced into a dense
+/// array indexed by key, other keys by hashing.
+pub fn reduce_by_key<T, K, V, G, H, F>(
+    inp: &[T],
+    get_key: G,
+    get_value: H,
+    identity: V,
+    combine: F
+) -> Vec<(K, V)>
+where
+    T: Copy + Default + Send + Sync,
+    K: PrimInt + Default + Send + Sync,
+    V: Copy + Default + Send + Sync,
+    G: Fn(T) -> K + Send + Sync + Copy + Clone,
+    H: Fn(T) -> V + Send + Sync + Copy + Clone,
+    F: Fn(V, V) -> V + Send + Sync + Copy + Clone
+{
+    let n = inp.len();
+    if n == 0 { return vec![]; }
+
+    let helper = ReduceHelper {
+        _p: PhantomData,
+        get_key,
+        get_value,
+        identity,
+        combine
+    };
+    let max_key = inp
+        .par_iter()
+        .map(|&a| get_key(a).to_usize().unwrap_or(usize::MAX))
+        .max()
+        .unwrap();
+
+    if max_key < n {
+        let mut sums: Vec<Option<V>> = vec![];
+        collect_reduce(inp, helper, max_key + 1, &mut sums);
+        sums
+            .into_par_iter()
+            .enumerate()
+            .filter_map(|(k, s)| s.map(|s| (K::from(k).unwrap(), s)))
+            .collect()
+    } else {
+        let mut res = vec![];
+        collect_reduce_sparse(inp, helper, &mut res);
+        res
+    }
+}
@@ -27,6 +27,7 @@
 use std::mem::size_of;
 use std::slice::from_raw_parts;
 use rayon::prelude::*;
+use num_traits::{PrimInt, Unsigned};
 use enhanced_rayon::prelude::*;
 
 use crate::{DefInt, maybe_uninit_vec};
@@ -37,7 +38,7 @@ const RADIX: usize = 8;
 const MAX_BUCKETS: usize = 1 << RADIX;
 
 
-fn seq_radix_sort_<T: Copy, F: Fn(T) -> DefInt>(
+fn seq_radix_sort_<T: Copy, K: PrimInt + Unsigned, F: Fn(T) -> K>(
     inp: &mut [T],
     out: &mut [T],
     g: &F,
@@ -52,20 +53,20 @@ fn seq_radix_sort_<T: Copy, F: Fn(T) -> DefInt>(
     let mut bits = bits;
     while bits > 0 {
         let round_bits = RADIX.min(bits);
-        let num_buckets = 1 << round_bits;
-        let mask = num_buckets - 1;
+        let num_buckets = 1usize << round_bits;
+        let mask = K::from(num_buckets - 1).unwrap();
 
         if swapped {
             let keys: Vec<_> = (0..n)
                 .map(|i| (g(out[i]) >> bit_offset) & mask)
                 .collect();
-            seq_count_sort_(out, inp, &keys, &mut counts, num_buckets as usize);
+            seq_count_sort_(out, inp, &keys, &mut counts, num_buckets);
         }
         else {
             let keys: Vec<_> = (0..n)
                 .map(|i| (g(inp[i]) >> bit_offset) & mask)
                 .collect();
-            seq_count_sort_(inp, out, &keys, &mut counts, num_buckets as usize);
+            seq_count_sort_(inp, out, &keys, &mut counts, num_buckets);
         }
 
         bits = bits - round_bits;
@@ -78,7 +79,7 @@ fn seq_radix_sort_<T: Copy, F: Fn(T) -> DefInt>(
 }
 
 
-pub fn seq_radix_sort<T: Copy, F: Fn(T) -> DefInt>(
+pub fn seq_radix_sort<T: Copy, K: PrimInt + Unsigned, F: Fn(T) -> K>(
     inp: &[T],
     out: &mut [T],
     tmp: &mut [T],
@@ -95,7 +96,7 @@ pub fn seq_radix_sort<T: Copy, F: Fn(T) -> DefInt>(
     }
 }
 
-pub fn integer_sort_r<T, F>(
+pub fn integer_sort_r<T, K, F>(
     inp: &[T],
     out: &mut [T],
     tmp: &mut [T],
@@ -104,7 +105,8 @@ pub fn integer_sort_r<T, F>(
     num_buckets: usize,
     parallelism: f32
 ) -> Vec<DefInt> where
-    F: Fn(T) -> DefInt + Sync + Send,
+    F: Fn(T) -> K + Sync + Send,
+    K: PrimInt + Unsigned + Send + Sync,
     T: Copy + Send + Sync,
 {
     let n = inp.len();
@@ -126,7 +128,7 @@ pub fn integer_sort_r<T, F>(
     }
     // single parallel count sort for few bits
     else if key_bits <= base_bits {
-        let mask = (1 << key_bits) - 1;
+        let mask = K::from((1usize << key_bits) - 1).unwrap();
         let get_bits: Vec<_> = inp
             .into_par_iter()
             .map(|&i| g(i) & mask)
@@ -143,7 +145,7 @@ pub fn integer_sort_r<T, F>(
         let num_outer_buckets = 1usize << bits;
         let num_inner_buckets =
             if return_offsets { 1usize << shift_bits } else { 0 };
-        let mask = num_outer_buckets as DefInt - 1;
+        let mask = K::from(num_outer_buckets - 1).unwrap();
         let f = |i: usize| { (g(inp[i]) >> shift_bits) & mask };
         let get_bits = (0..n).into_par_iter().map(f).collect::<Vec<_>>();
 
@@ -193,7 +195,7 @@ pub fn integer_sort_r<T, F>(
     }
 }
 
-pub fn integer_sort_<T, F>(
+pub fn integer_sort_<T, K, F>(
     inp: &[T],
     out: &mut [T],
     tmp: &mut [T],
@@ -201,28 +203,30 @@ pub fn integer_sort_<T, F>(
     mut bits: usize,
     num_buckets: usize
 ) -> Vec<DefInt> where
-    F: Fn(T) -> DefInt + Sync + Send,
+    F: Fn(T) -> K + Sync + Send,
+    K: PrimInt + Unsigned + Send + Sync,
     T: Copy + Send + Sync,
 {
     if bits == 0 {
-        bits = log2_up(
-            inp
-                .par_iter()
-                .map(|&k| get_key(k) as DefInt)
-                .max()
-                .unwrap() as usize
-            );
+        // number of significant bits of the largest key
+        let max_key = inp
+            .par_iter()
+            .map(|&k| get_key(k))
+            .max()
+            .unwrap_or(K::zero());
+        bits = 8 * size_of::<K>() - max_key.leading_zeros() as usize;
     }
     integer_sort_r(inp, out, tmp, get_key, bits, num_buckets, 1.0)
 }
 
-pub fn integer_sort<T, F>(
+pub fn integer_sort<T, K, F>(
     inp: &[T],
     get_key: &F,
     bits: usize,
     out: &mut Vec<T>
 ) where
-    F: Fn(T) -> DefInt + Sync + Send,
+    F: Fn(T) -> K + Sync + Send,
+    K: PrimInt + Unsigned + Send + Sync,
     T: Copy + Send + Sync,
 {
     if inp.len() == 0 {
@@ -233,3 +237,60 @@ pub fn integer_sort<T, F>(
         integer_sort_(inp, out, &mut tmp, get_key, bits, 0);
     }
 }
+
+
+/// Maps a value to an unsigned key such that comparing the keys gives the
+/// same order as comparing the values, so that signed integers and floats
+/// can be sorted with `integer_sort` by their bit patterns.
+pub trait OrderedKey: Copy {
+    type Key: PrimInt + Unsigned + Send + Sync;
+
+    fn ordered_key(self) -> Self::Key;
+}
+
+macro_rules! ordered_key_unsigned {
+    ($($t:ty),*) => {$(
+        impl OrderedKey for $t {
+            type Key = $t;
+
+            #[inline(always)]
+            fn ordered_key(self) -> Self::Key { self }
+        }
+    )*};
+}
+
+// flipping the sign bit moves the negative numbers below the positive ones
+macro_rules! ordered_key_signed {
+    ($(($t:ty, $u:ty)),*) => {$(
+        impl OrderedKey for $t {
+            type Key = $u;
+
+            #[inline(always)]
+            fn ordered_key(self) -> Self::Key {
+                (self as $u) ^ (1 << (<$u>::BITS - 1))
+            }
+        }
+    )*};
+}
+
+// for negative floats all the bits are flipped, since a larger magnitude
+// means a smaller number. NaNs with the sign bit set go first and the
+// others go last.
+macro_rules! ordered_key_float {
+    ($(($t:ty, $u:ty)),*) => {$(
+        impl OrderedKey for $t {
+            type Key = $u;
+
+            #[inline(always)]
+            fn ordered_key(self) -> Self::Key {
+                let b = self.to_bits();
+                let sign = 1 << (<$u>::BITS - 1);
+                if b & sign != 0 { !b } else { b | sign }
+            }
+        }
+    )*};
+}
+
+ordered_key_unsigned!(u8, u16, u32, u64, usize);
+ordered_key_signed!((i8, u8), (i16, u16), (i32, u32), (i64, u64), (isize, usize));
+ordered_key_float!((f32, u32), (f64, u64));
@@ -24,69 +24,143 @@
 // SOFTWARE.
 // ============================================================================
 
+use std::ptr;
+use std::mem::MaybeUninit;
 use rayon::prelude::*;
 
-use crate::internal::binary_search::binary_search;
+use crate::maybe_uninit_vec;
+use crate::utilities::as_uninit_mut;
+use crate::internal::binary_search::binary_search_by;
 
 const MERGE_BASE: usize = 2000;
 
 
-pub(crate) fn seq_merge<T, F>(in1: &[T], in2: &[T], out: &mut [T], less: F)
-where
-    T: Copy,
-    F: Fn(T, T) -> bool,
+/// Merges the sorted `in1` and `in2` into `out` by moving (bitwise copying)
+/// the elements; the inputs must not be used (or dropped) afterwards.
+pub(crate) unsafe fn seq_merge_by<T, F>(
+    in1: &[T],
+    in2: &[T],
+    out: &mut [MaybeUninit<T>],
+    less: &F
+) where
+    F: Fn(&T, &T) -> bool,
 {
     let (n1, n2) = (in1.len(), in2.len());
     debug_assert!(n1 + n2 <= out.len());
+    let o = out.as_mut_ptr() as *mut T;
     let (mut i, mut j) = (0, 0);
 
-    loop {
-        if i == n1 { // if inp1 has no more elements
-            for j in j..n2 { out[i+j] = in2[j]; }
-            break;
-        }
-        if j == n2 { // if inp2 has no more elements
-            for i in i..n1 { out[i+j] = in1[i]; }
-            break;
-        }
-
-        let oi = &mut out[i+j];
-        if less(in2[j], in1[i]) {
-            *oi = in2[j];
+    while i < n1 && j < n2 {
+        if less(&in2[j], &in1[i]) {
+            ptr::copy_nonoverlapping(&in2[j], o.add(i+j), 1);
             j += 1;
         } else {
-            *oi = in1[i];
-            i+=1;
+            ptr::copy_nonoverlapping(&in1[i], o.add(i+j), 1);
+            i += 1;
         }
     }
+    // at most one of the inputs has elements left
+    ptr::copy_nonoverlapping(in1.as_ptr().add(i), o.add(i+j), n1 - i);
+    ptr::copy_nonoverlapping(in2.as_ptr().add(j), o.add(i+j), n2 - j);
 }
 
-pub(crate) fn merge_into<T, F>(in1: &[T], in2: &[T], out: &mut [T], less: F)
-where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Clone + Send,
+/// Moves (bitwise copies) `inp` into `out` in parallel.
+unsafe fn par_move_into<T: Send + Sync>(inp: &[T], out: &mut [MaybeUninit<T>]) {
+    out
+        .par_chunks_mut(MERGE_BASE)
+        .zip(inp.par_chunks(MERGE_BASE))
+        .for_each(|(o, i)| ptr::copy_nonoverlapping(
+            i.as_ptr(),
+            o.as_mut_ptr() as *mut T,
+            i.len()
+        ));
+}
+
+/// Parallel version of `seq_merge_by`.
+pub(crate) unsafe fn merge_into_by<T, F>(
+    in1: &[T],
+    in2: &[T],
+    out: &mut [MaybeUninit<T>],
+    less: F
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Clone + Send,
 {
     let (n1, n2) = (in1.len(), in2.len());
     let no = n1 + n2;
     debug_assert_eq!(no, out.len());
 
     if no < MERGE_BASE {
-        seq_merge(in1, in2, out, less);
+        seq_merge_by(in1, in2, out, &less);
     }
-    else if n1 == 0 {
-        out.par_iter_mut().zip(in2.par_iter()).for_each(|(o, i)| *o = *i); }
-    else if n2 == 0 {
-        out.par_iter_mut().zip(in1.par_iter()).for_each(|(o, i)| *o = *i); }
+    else if n1 == 0 { par_move_into(in2, out); }
+    else if n2 == 0 { par_move_into(in1, out); }
     else {
         let mut m1 = n1 / 2;
-        let m2 = binary_search(in2, in1[m1], less.clone());
+        let m2 = binary_search_by(in2, &in1[m1], &less);
         if m2 == 0 { m1 += 1; }
         let mo = m1 + m2;
         let (l_out, r_out) = out.split_at_mut(mo);
         let less_clone = less.clone();
         rayon::join(
-            || merge_into(&in1[0..m1], &in2[0..m2], l_out, less_clone),
-            || merge_into(&in1[m1..n1], &in2[m2..n2], r_out, less),
+            || merge_into_by(&in1[0..m1], &in2[0..m2], l_out, less_clone),
+            || merge_into_by(&in1[m1..n1], &in2[m2..n2], r_out, less),
         );
     }
 }
+
+/// Merges the sorted `a` and `b` into `out`, which must have room for
+/// exactly `a.len() + b.len()` elements. The merge is stable: among equal
+/// elements, the ones from `a` come first.
+pub fn merge<T, F>(a: &[T], b: &[T], out: &mut [T], less: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Send + Sync,
+{
+    assert_eq!(a.len() + b.len(), out.len(), "merge: wrong output size");
+    unsafe { merge_into_by(a, b, as_uninit_mut(out), |x: &T, y: &T| less(*x, *y)); }
+}
+
+/// merges `runs` into `out`, using `tmp` (of the same size) as scratch space
+fn kway_merge_<T, F>(runs: &[&[T]], out: &mut [T], tmp: &mut [T], less: &F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    match runs.len() {
+        0 => {},
+        1 => out.par_iter_mut().zip(runs[0].par_iter()).for_each(|(o, i)| *o = *i),
+        2 => unsafe { merge_into_by(runs[0], runs[1], as_uninit_mut(out), less) },
+        k => {
+            // merge each half of the runs into tmp, then merge the halves
+            let h = k / 2;
+            let m: usize = runs[..h].iter().map(|r| r.len()).sum();
+            let (l_out, r_out) = out.split_at_mut(m);
+            let (l_tmp, r_tmp) = tmp.split_at_mut(m);
+            rayon::join(
+                || kway_merge_(&runs[..h], l_tmp, l_out, less),
+                || kway_merge_(&runs[h..], r_tmp, r_out, less),
+            );
+            unsafe { merge_into_by(l_tmp, r_tmp, as_uninit_mut(out), less); }
+        }
+    }
+}
+
+/// Merges the sorted `runs` into `out`, which must have room for exactly
+/// all of their elements, in O(n log k) work. The merge is stable: equal
+/// elements keep the order of their runs, and their order within a run.
+pub fn kway_merge<T, F>(runs: &[&[T]], out: &mut [T], less: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Send + Sync,
+{
+    let n: usize = runs.iter().map(|r| r.len()).sum();
+    assert_eq!(n, out.len(), "kway_merge: wrong output size");
+    if n == 0 { return; }
+
+    let mut tmp = maybe_uninit_vec![
+        runs.iter().find(|r| !r.is_empty()).unwrap()[0];
+        n
+    ];
+    kway_merge_(runs, out, &mut tmp, &|x: &T, y: &T| less(*x, *y));
+}
@@ -24,40 +24,54 @@
 // SOFTWARE.
 // ============================================================================
 
-use crate::internal::merge::merge_into;
+use std::ptr;
+use std::mem::MaybeUninit;
+
+use crate::internal::merge::merge_into_by;
 use crate::maybe_uninit_vec;
+use crate::utilities::{
+    AbortOnPanic, uninit_buffer, as_uninit_mut, assume_init_ref
+};
 
-use crate::internal::quick_sort::insertion_sort;
+use crate::internal::quick_sort::insertion_sort_by;
 
 const MERGE_SORT_BASE: usize = 48;
 
-pub(crate) fn merge_sort_<T, F>(
+/// Sorts `inp` leaving the result in `inp` if `inplace` and in `out`
+/// otherwise. `out` starts uninitialized. The elements are moved with
+/// bitwise copies, so whichever of the two does not hold the result must
+/// be treated as uninitialized.
+pub(crate) unsafe fn merge_sort_by_<T, F>(
     inp: &mut [T],
-    out: &mut [T],
+    out: &mut [MaybeUninit<T>],
     less: F,
     inplace: bool
 ) where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Clone + Send,
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Clone + Send,
 {
     let n = inp.len();
     if n < MERGE_SORT_BASE {
-        insertion_sort(inp, less);
-        if !inplace { out.iter_mut().zip(inp.iter()).for_each(|(o, i)| *o = *i); }
+        insertion_sort_by(inp, &less);
+        if !inplace {
+            ptr::copy_nonoverlapping(inp.as_ptr(), out.as_mut_ptr() as *mut T, n);
+        }
     } else {
         let m = n / 2;
         let (l_inp, r_inp) = inp.split_at_mut(m);
         let (l_out, r_out) = out.split_at_mut(m);
-        let (cmp_clone_1, cmp_clone_2) = (less.clone(), less.clone());
-        let l = || merge_sort_(l_inp, l_out, cmp_clone_1, !inplace);
-        let r = || merge_sort_(r_inp, r_out, cmp_clone_2, !inplace);
+        let (less_1, less_2) = (less.clone(), less.clone());
+        let l = || merge_sort_by_(l_inp, l_out, less_1, !inplace);
+        let r = || merge_sort_by_(r_inp, r_out, less_2, !inplace);
         if n > 64 { rayon::join(l, r); }
         else { l(); r(); }
 
         if inplace {
-            merge_into(&out[0..m], &out[m..n], inp, less);
+            // the halves were sorted into `out`
+            let out = assume_init_ref(out);
+            merge_into_by(&out[0..m], &out[m..n], as_uninit_mut(inp), less);
         } else {
-            merge_into(&inp[0..m], &inp[m..n], out, less);
+            merge_into_by(&inp[0..m], &inp[m..n], out, less);
         }
     }
 }
@@ -67,11 +81,12 @@ where
     T: Copy + Send + Sync,
     F: Fn(T, T) -> bool + Clone + Send,
 {
+    let less = move |a: &T, b: &T| less(*a, *b);
     let n = inp.len();
-    if n < MERGE_SORT_BASE { insertion_sort(inp, less);}
+    if n < MERGE_SORT_BASE { insertion_sort_by(inp, &less);}
     else {
         let mut out = maybe_uninit_vec![inp[0]; n];
-        merge_sort_(inp, &mut out, less, true);
+        unsafe { merge_sort_by_(inp, as_uninit_mut(&mut out), less, true); }
     }
 }
 
@@ -80,5 +95,22 @@ where
     T: Copy + Send + Sync,
     F: Fn(T, T) -> bool + Clone + Send,
 {
-    merge_sort_(inp, out, less, false);
+    let less = move |a: &T, b: &T| less(*a, *b);
+    unsafe { merge_sort_by_(inp, as_uninit_mut(out), less, false); }
+}
+
+/// Stable parallel merge sort of non-`Copy` elements.
+pub fn nc_merge_sort_inplace<T, F>(inp: &mut [T], less: F)
+where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    let n = inp.len();
+    if n < MERGE_SORT_BASE { insertion_sort_by(inp, &less); }
+    else {
+        let _guard = AbortOnPanic;
+        // dropping the buffer never drops elements
+        let mut buf = uninit_buffer(n);
+        unsafe { merge_sort_by_(inp, &mut buf, &less, true); }
+    }
 }
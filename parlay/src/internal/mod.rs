@@ -40,10 +40,13 @@ mod quick_sort;
 pub use quick_sort::*;
 
 mod bucket_sort;
-pub use bucket_sort::bucket_sort;
+pub use bucket_sort::{bucket_sort, nc_bucket_sort};
 
 mod sample_sort;
 pub use sample_sort::*;
 
+mod select;
+pub use select::*;
+
 mod get_time;
 pub use get_time::Timer;
@@ -36,14 +36,13 @@ fn base_case<T>(inp: &[T]) -> bool {
 }
 
 /// Simple serial insertion sort
-pub(crate) fn insertion_sort<T, F>(inp: &mut [T], less: F)
+pub(crate) fn insertion_sort_by<T, F>(inp: &mut [T], less: &F)
 where
-    T: Copy,
-    F: Fn(T, T) -> bool,
+    F: Fn(&T, &T) -> bool,
 {
     for i in 1..inp.len() {
         let mut j = i;
-        while j > 0 && less(inp[j], inp[j-1]) {
+        while j > 0 && less(&inp[j], &inp[j-1]) {
             inp.swap(j, j-1);
             j-=1;
         }
@@ -51,40 +50,37 @@ where
 }
 
 /// sorts 5 elements taken at even stride and puts them at the front
-fn sort5<T, F>(inp: &mut [T], less: F)
+fn sort5<T, F>(inp: &mut [T], less: &F)
 where
-    T: Copy,
-    F: Fn(T, T) -> bool,
+    F: Fn(&T, &T) -> bool,
 {
     let size = 5;
     let m = inp.len() / (size + 1);
     for l in 0..size { inp.swap(l, m * (l+1)); }
-    insertion_sort(&mut inp[..size], less);
+    insertion_sort_by(&mut inp[..size], less);
 }
 
 /// Dual-pivot partition. Picks two pivots from the input A
 /// and then divides it into three parts:
 ///   [x < p1), [p1 <= x <= p2], (p2 < x]
-fn split3<T, F>(inp: &mut [T], less: F) -> (usize, usize, bool)
+fn split3<T, F>(inp: &mut [T], less: &F) -> (usize, usize, bool)
 where
-    T: Copy,
-    F: Fn(T, T) -> bool + Copy,
+    F: Fn(&T, &T) -> bool,
 {
     let n = inp.len();
     sort5(inp, less);
-    let _inp_ptr = inp.as_ptr() as usize;
 
     // Use A[1] and A[3] as the pivots. Move them to
     // the front so that A[0] and A[1] are the pivots
+    // (they stay there until the end, so they are read in place)
     inp.swap(0, 1); inp.swap(1, 3);
-    let (p1, p2) = (inp[0], inp[1]);
-    let pivots_equal = !less(p1, p2);
+    let pivots_equal = !less(&inp[0], &inp[1]);
 
     // set up initial invariants
     let mut li = 2;
     let mut ri = n - 1;
-    while less(inp[li], p1) { li+=1 };
-    while less(p2, inp[ri]) { ri-=1 };
+    while less(&inp[li], &inp[0]) { li+=1 };
+    while less(&inp[1], &inp[ri]) { ri-=1 };
     let mut mi = li;
 
     // invariants:
@@ -93,17 +89,17 @@ where
     //  between li and mi are between p1 and p2 inclusive
     //  between mi and ri are unprocessed
     while mi <= ri {
-        if less(inp[mi], p1) {
+        if less(&inp[mi], &inp[0]) {
             inp.swap(mi, li);
             li+=1;
-        } else if less(p2, inp[mi]) {
+        } else if less(&inp[1], &inp[mi]) {
             inp.swap(mi, ri);
-            if less(inp[mi], p1) {
+            if less(&inp[mi], &inp[0]) {
                 inp.swap(li, mi);
                 li+=1;
             }
             ri-=1;
-            while less(p2, inp[ri]) { ri-=1; }
+            while less(&inp[1], &inp[ri]) { ri-=1; }
         }
         mi+=1;
     }
@@ -114,34 +110,31 @@ where
     (li, mi, pivots_equal)
 }
 
-/// Serial quick sort
-pub fn quick_sort_serial<T, F>(inp: &mut [T], less: F)
+fn quick_sort_serial_by<T, F>(inp: &mut [T], less: &F)
 where
-    T: Copy,
-    F: Fn(T, T) -> bool + Copy,
+    F: Fn(&T, &T) -> bool,
 {
     let mut n = inp.len();
     while !base_case(&inp[..n]) {
         let (l, m, mid_eq) = split3(&mut inp[..n], less);
         if !mid_eq {
-            quick_sort_serial(&mut inp[l+1..m], less)
+            quick_sort_serial_by(&mut inp[l+1..m], less)
         };
-        quick_sort_serial(&mut inp[m..n], less);
+        quick_sort_serial_by(&mut inp[m..n], less);
         n = l;
     }
 
-    insertion_sort(inp, less);
+    insertion_sort_by(inp, less);
 }
 
-/// Parallel quick sort
-pub fn quick_sort<T, F>(inp: &mut [T], less: F)
+pub(crate) fn quick_sort_by<T, F>(inp: &mut [T], less: &F)
 where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
 {
     // serial sort for small inputs
     if inp.len() < SERIAL_QS_TR {
-        quick_sort_serial(inp, less);
+        quick_sort_serial_by(inp, less);
     } else {  // parallel sort for large enough inputs
         let (l, m, mid_eq) = split3(inp, less);
 
@@ -149,9 +142,9 @@ where
         let (t2, r_inp) = t1.split_at_mut(m-l);
         let m_inp = &mut t2[1..];
 
-        let left = || quick_sort(l_inp, less);
-        let mid = || quick_sort(m_inp, less);
-        let right = || quick_sort(r_inp, less);
+        let left = || quick_sort_by(l_inp, less);
+        let mid = || quick_sort_by(m_inp, less);
+        let right = || quick_sort_by(r_inp, less);
 
         if mid_eq {
             rayon::join(left, right);
@@ -161,3 +154,30 @@ where
         }
     }
 }
+
+/// Serial quick sort
+pub fn quick_sort_serial<T, F>(inp: &mut [T], less: F)
+where
+    T: Copy,
+    F: Fn(T, T) -> bool + Copy,
+{
+    quick_sort_serial_by(inp, &|a: &T, b: &T| less(*a, *b));
+}
+
+/// Parallel quick sort
+pub fn quick_sort<T, F>(inp: &mut [T], less: F)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    quick_sort_by(inp, &|a: &T, b: &T| less(*a, *b));
+}
+
+/// Parallel quick sort of non-`Copy` elements (not stable).
+pub fn nc_quick_sort<T, F>(inp: &mut [T], less: F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    quick_sort_by(inp, &less);
+}
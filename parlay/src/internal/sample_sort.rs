@@ -24,43 +24,46 @@
 // SOFTWARE.
 // ============================================================================
 
-use std::mem::size_of;
+use std::ptr;
+use std::mem::{size_of, ManuallyDrop, MaybeUninit};
 use rayon::prelude::*;
 use enhanced_rayon::prelude::*;
 
-use crate::maybe_uninit_vec;
-use crate::utilities::{log2_up, hash64};
-use crate::internal::{quick_sort, bucket_sort};
+use crate::utilities::{
+    log2_up, hash64, AbortOnPanic, uninit_buffer, as_uninit_mut,
+    assume_init_mut, assume_init_ref
+};
+use crate::internal::quick_sort::quick_sort_by;
+use crate::internal::bucket_sort::bucket_sort_r;
 use crate::internal::transpose::transpose_buckets;
 
 
 const QUICKSORT_THRESHOLD: usize = 16384;
-const OVER_SAMPLE: usize = 8;
+pub(crate) const OVER_SAMPLE: usize = 8;
 
 
 fn get_bucket_counts<T, F>(
     arr: &[T],
-    pivots: &[T],
+    pivots: &[ManuallyDrop<T>],
     counts: &mut[usize],
-    less: F
+    less: &F
 ) where
-    T: Copy,
-    F: Fn(T, T) -> bool + Copy,
+    F: Fn(&T, &T) -> bool,
 {
     if arr.len() == 0 || pivots.len() == 0 { return; }
     counts.iter_mut().for_each(|i| *i=0);
     let (mut ai, mut pi, mut ci) = (0, 0, 0);
     let (a_end, p_end, c_end) = (arr.len(), pivots.len(), counts.len());
     loop {
-        while less(arr[ai], pivots[pi]) {
+        while less(&arr[ai], &pivots[pi]) {
             debug_assert_ne!(ci, c_end);
             counts[ci] += 1;
             ai += 1; if ai == a_end { return; }
         }
         pi += 1; ci += 1;
         if pi == p_end { break; }
-        if !less(pivots[pi-1], pivots[pi]) {
-            while !less(pivots[pi], arr[ai]) {
+        if !less(&pivots[pi-1], &pivots[pi]) {
+            while !less(&pivots[pi], &arr[ai]) {
                 debug_assert_ne!(ci, c_end);
                 counts[ci] += 1;
                 ai += 1; if ai == a_end { return; }
@@ -73,31 +76,47 @@ fn get_bucket_counts<T, F>(
     counts[ci] = a_end - ai;
 }
 
-fn seq_sort_inplace<T, F>(inp: &mut [T], less: F, stable: bool)
+fn seq_sort_inplace<T, F>(inp: &mut [T], less: &F, stable: bool)
 where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
 {
-    if size_of::<T>() > 8 {
-        if !stable { quick_sort(inp, less); }
-        else { bucket_sort(inp, less, true); }
+    let n = inp.len();
+    if !stable && size_of::<T>() > 8 { quick_sort_by(inp, less); }
+    else if n > 1 {
+        // dropping the buffer never drops elements
+        let mut tmp = uninit_buffer(n);
+        unsafe { bucket_sort_r(inp, &mut tmp, less, stable, true); }
     }
-    else { bucket_sort(inp, less, stable); }
 }
 
-fn seq_sort_<T, F>(inp: &[T], out: &mut [T], less: F, stable: bool)
-where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
+/// moves (bitwise copies) `inp` to `out` and sorts it there
+unsafe fn seq_sort_<T, F>(
+    inp: &[T],
+    out: &mut [MaybeUninit<T>],
+    less: &F,
+    stable: bool
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
 {
-    out.copy_from_slice(inp);
-    seq_sort_inplace(out, less, stable);
+    if inp.as_ptr() != out.as_ptr() as *const T {
+        ptr::copy_nonoverlapping(inp.as_ptr(), out.as_mut_ptr() as *mut T, inp.len());
+    }
+    seq_sort_inplace(assume_init_mut(out), less, stable);
 }
 
-pub fn sample_sort<T, F>(inp: &[T], out: &mut [T], less: F, stable: bool)
-where
-    T: Copy + Send + Sync,
-    F: Fn(T, T) -> bool + Copy + Send + Sync,
+/// Moves the elements of `inp` to `out` in sorted order. `inp` and `out`
+/// may be the same slice; otherwise `inp` must be treated as uninitialized
+/// afterwards.
+unsafe fn sample_sort_by_<T, F>(
+    inp: &[T],
+    out: &mut [MaybeUninit<T>],
+    less: &F,
+    stable: bool
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
 {
     let n = inp.len();
     if n < QUICKSORT_THRESHOLD {
@@ -112,36 +131,38 @@ where
         let sample_set_size = num_buckets * OVER_SAMPLE;
         let m = num_blocks * num_buckets;
 
-        // generate and sort random samples
+        // generate and sort random samples. The samples and pivots are
+        // bitwise copies of input elements that are only compared, never
+        // dropped, while the elements themselves are moved around
         let mut sample_set: Vec<_> = (0..sample_set_size as u64)
             .into_par_iter()
-            .map(|i| inp[hash64(i) as usize % n])
+            .map(|i| ManuallyDrop::new(ptr::read(&inp[hash64(i) as usize % n])))
             .collect();
-        quick_sort(&mut sample_set, less);
-        let pivots: Vec<T> = (0..num_buckets-1)
-            .map(|i| sample_set[i * OVER_SAMPLE])
+        let less_md = |a: &ManuallyDrop<T>, b: &ManuallyDrop<T>| less(a, b);
+        quick_sort_by(&mut sample_set, &less_md);
+        let pivots: Vec<ManuallyDrop<T>> = (0..num_buckets-1)
+            .map(|i| ptr::read(&sample_set[i * OVER_SAMPLE]))
             .collect();
 
-        let mut tmp = maybe_uninit_vec![inp[0]; n];
-        let mut counts = maybe_uninit_vec![0usize; m+1];
-        counts[m] = 0;
+        let mut tmp = uninit_buffer(n);
+        let mut counts = vec![0usize; m+1];
 
         // sort each block and merge with samples to get counts for each bucket
         (
-            (&inp).par_chunks(block_size),
-            (&mut tmp).par_chunks_mut(block_size),
-            (&mut counts).par_chunks_mut(num_buckets)
+            inp.par_chunks(block_size),
+            tmp.par_chunks_mut(block_size),
+            counts.par_chunks_mut(num_buckets)
         )
             .into_par_iter()
             .for_each(|(inp, tmp, cnt)| {
-                seq_sort_(&inp, tmp, less, stable);
-                get_bucket_counts(tmp, &pivots, cnt, less);
+                seq_sort_(inp, tmp, less, stable);
+                get_bucket_counts(assume_init_ref(tmp), &pivots, cnt, less);
             });
 
         // move data from blocks to buckets
         let mut bucket_offsets = Vec::<usize>::new();
         transpose_buckets(
-            &tmp,
+            assume_init_ref(&tmp),
             out,
             &mut counts,
             &mut bucket_offsets,
@@ -152,17 +173,27 @@ where
         );
 
         // sort within each bucket
-        out
+        assume_init_mut(out)
             .par_ind_chunks_mut(&bucket_offsets[..num_buckets])
             .enumerate()
             .for_each(|(i, out)| {
-                if i==0 || i==num_buckets-1 || less(pivots[i-1], pivots[i]) {
+                if i==0 || i==num_buckets-1 || less(&pivots[i-1], &pivots[i]) {
                     seq_sort_inplace(out, less, stable);
                 }
             });
     }
 }
 
+pub fn sample_sort<T, F>(inp: &[T], out: &mut [T], less: F, stable: bool)
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    unsafe {
+        sample_sort_by_(inp, as_uninit_mut(out), &|a: &T, b: &T| less(*a, *b), stable);
+    }
+}
+
 pub fn sample_sort_inplace<T, F>(arr: &mut [T], less: F, stable: bool)
 where
     T: Copy + Send + Sync,
@@ -171,3 +202,14 @@ where
     let a_shadow = unsafe { (arr as *const [T]).as_ref().unwrap() };
     sample_sort(a_shadow, arr, less, stable);
 }
+
+/// Parallel sample sort of non-`Copy` elements.
+pub fn nc_sample_sort_inplace<T, F>(arr: &mut [T], less: F, stable: bool)
+where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    let _guard = AbortOnPanic;
+    let a_shadow = unsafe { (arr as *const [T]).as_ref().unwrap() };
+    unsafe { sample_sort_by_(a_shadow, as_uninit_mut(arr), &less, stable); }
+}
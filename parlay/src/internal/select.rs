@@ -0,0 +1,137 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use crate::primitives::filter;
+use crate::utilities::hash64;
+use crate::internal::quick_sort;
+use crate::internal::sample_sort::{sample_sort_inplace, OVER_SAMPLE};
+
+const SELECT_SEQ_THR: usize = 16384;
+
+
+/// Returns the element of rank `k` (0-based) of `arr` according to `less`,
+/// i.e. the element that would be at position `k` if `arr` was sorted.
+/// Panics if `k >= arr.len()`.
+pub fn kth_smallest<T, F>(arr: &[T], k: usize, less: F) -> T
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    assert!(k < arr.len(), "kth_smallest: k is out of range");
+    let mut a = arr.to_vec();
+    let mut k = k;
+    let mut round = 0u64;
+
+    loop {
+        let n = a.len();
+        if n < SELECT_SEQ_THR {
+            a.select_nth_unstable_by(k, |&x, &y| {
+                if less(x, y) { std::cmp::Ordering::Less }
+                else if less(y, x) { std::cmp::Ordering::Greater }
+                else { std::cmp::Ordering::Equal }
+            });
+            return a[k];
+        }
+
+        // pick two pivots from a sorted sample so that the element of rank
+        // k falls between them with high probability
+        let m = OVER_SAMPLE * (n as f64).sqrt() as usize;
+        let mut sample: Vec<T> = (0..m as u64)
+            .into_par_iter()
+            .map(|i| a[hash64(round * m as u64 + i) as usize % n])
+            .collect();
+        quick_sort(&mut sample, less);
+        let r = k * m / n;
+        let d = (m as f64).sqrt() as usize;
+        let (lo, hi) = (sample[r.saturating_sub(d)], sample[(r + d).min(m - 1)]);
+
+        let n_lo = a.par_iter().filter(|&&x| less(x, lo)).count();
+        let n_hi = a.par_iter().filter(|&&x| less(hi, x)).count();
+
+        if k < n_lo {
+            a = filter(&a, |&x| less(x, lo));
+        } else if k >= n - n_hi {
+            k -= n - n_hi;
+            a = filter(&a, |&x| less(hi, x));
+        } else if n_lo + n_hi > 0 {
+            // all the elements in between are equal
+            if !less(lo, hi) { return lo; }
+            k -= n_lo;
+            a = filter(&a, |&x| !less(x, lo) && !less(hi, x));
+        } else {
+            // the pivots are the minimum and the maximum, so split around
+            // a single pivot instead (this always removes some elements)
+            let p = sample[r];
+            let n_lt = a.par_iter().filter(|&&x| less(x, p)).count();
+            let n_gt = a.par_iter().filter(|&&x| less(p, x)).count();
+            if k < n_lt {
+                a = filter(&a, |&x| less(x, p));
+            } else if k < n - n_gt {
+                return p;
+            } else {
+                k -= n - n_gt;
+                a = filter(&a, |&x| less(p, x));
+            }
+        }
+        round += 1;
+    }
+}
+
+/// Returns the `k` smallest elements of `arr` according to `less`, in
+/// sorted order (or all of them if `k >= arr.len()`).
+pub fn top_k<T, F>(arr: &[T], k: usize, less: F) -> Vec<T>
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    let n = arr.len();
+    if k == 0 { return vec![]; }
+
+    let mut r = if k >= n { arr.to_vec() } else {
+        let p = kth_smallest(arr, k - 1, less);
+        // all the elements smaller than the pivot, then enough copies of it
+        let mut r = filter(arr, |&x| less(x, p));
+        let eq = filter(arr, |&x| !less(x, p) && !less(p, x));
+        let c = r.len();
+        r.extend_from_slice(&eq[..k - c]);
+        r
+    };
+    sample_sort_inplace(&mut r, less, false);
+    r
+}
+
+/// Returns the (lower) median of `arr` according to `less`. Panics if `arr`
+/// is empty.
+pub fn median<T, F>(arr: &[T], less: F) -> T
+where
+    T: Copy + Send + Sync,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    assert!(!arr.is_empty(), "median: empty input");
+    kth_smallest(arr, (arr.len() - 1) / 2, less)
+}
@@ -24,6 +24,7 @@
 // SOFTWARE.
 // ============================================================================
 
+use std::mem::MaybeUninit;
 use rayon::prelude::*;
 
 use crate::maybe_uninit_vec;
@@ -90,14 +91,14 @@ impl<'a, T: Copy + Send + Sync> Transpose<'a, T>
 
 struct BlockTrans<'a, T> {
     a: &'a [T],
-    b: &'a mut [T],
+    b: &'a mut [MaybeUninit<T>],
     oa: &'a [usize],
     ob: &'a [usize]
 }
 
-impl<'a, T: Copy + Send + Sync> BlockTrans<'a, T>
+impl<'a, T: Send + Sync> BlockTrans<'a, T>
 {
-    fn new(a: &'a [T], b: &'a mut [T], oa: &'a [usize], ob: &'a [usize]) -> Self
+    fn new(a: &'a [T], b: &'a mut [MaybeUninit<T>], oa: &'a [usize], ob: &'a [usize]) -> Self
     {
         Self { a, b, oa, ob }
     }
@@ -119,7 +120,7 @@ impl<'a, T: Copy + Send + Sync> BlockTrans<'a, T>
                     let sb = self.ob[j*c_length + i];
                     let l = self.oa[i*r_length + j + 1] - sa;
                     for k in 0..l { unsafe {
-                        (b_ptr as *mut T).add(sb + k).write(self.a[sa + k]);
+                        (b_ptr as *mut T).add(sb + k).write(std::ptr::read(&self.a[sa + k]));
                     }}
                 }
             });
@@ -147,9 +148,10 @@ impl<'a, T: Copy + Send + Sync> BlockTrans<'a, T>
     }
 }
 
-pub(crate) fn transpose_buckets<T: Copy + Send + Sync>(
+/// Moves (bitwise copies) the elements of `from` to `to`.
+pub(crate) fn transpose_buckets<T: Send + Sync>(
     from: &[T],
-    to: &mut [T],
+    to: &mut [MaybeUninit<T>],
     counts: &mut [usize],
     offsets: &mut Vec<usize>,
     n: usize,
@@ -187,7 +189,7 @@ pub(crate) fn transpose_buckets<T: Copy + Send + Sync>(
                 let len = counts[i * num_buckets + j];
                 for _ in 0..len {
                     unsafe {
-                        (to_ptr as *mut T).add(d_offset).write(from[s_offset]);
+                        (to_ptr as *mut T).add(d_offset).write(std::ptr::read(&from[s_offset]));
                     }
                     d_offset+=1; s_offset+=1;
                 }
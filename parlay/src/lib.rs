@@ -32,6 +32,7 @@ pub mod random;
 pub mod utilities;
 pub mod primitives;
 pub mod hash_table;
+pub mod spec_for;
 
 pub use internal::Timer;
 
@@ -179,6 +179,232 @@ where
     }
 }
 
+/* -------------------- Filter -------------------- */
+/// Keeps the elements of every block of `arr` that satisfy `pred` and
+/// returns them together with the (exclusive) offsets of the blocks in the
+/// output and the output size.
+fn filter_blocks<T, F>(arr: &[T], pred: F) -> (Vec<Vec<T>>, Vec<usize>, usize)
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    let bls = _BLOCK_SIZE * 10;
+    let blocks: Vec<Vec<T>> = arr
+        .par_chunks(bls)
+        .map(|chunk| chunk.iter().filter(|x| pred(x)).copied().collect())
+        .collect();
+
+    let mut sums: Vec<usize> = blocks.iter().map(Vec::len).collect();
+    let m = scan_inplace(&mut sums, false, |a, b| a + b);
+    (blocks, sums, m)
+}
+
+/// Writes the elements of `arr` that satisfy `pred` to the front of `dest`
+/// (in order) and returns their number. Panics if `dest` is too small to
+/// hold them.
+pub fn filter_into<T, F>(arr: &[T], pred: F, dest: &mut [T]) -> usize
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    let n = arr.len();
+    if num_blocks(n, _BLOCK_SIZE * 10) <= 1 {
+        let mut k = 0;
+        for x in arr.iter().filter(|x| pred(x)) {
+            assert!(k < dest.len(), "filter_into: destination is too small");
+            dest[k] = *x;
+            k += 1;
+        }
+        return k;
+    }
+
+    let (blocks, sums, m) = filter_blocks(arr, pred);
+    assert!(m <= dest.len(), "filter_into: destination is too small");
+    dest[..m]
+        .par_ind_chunks_mut(&sums)
+        .zip(blocks.par_iter())
+        .for_each(|(out_chunk, block)| out_chunk.copy_from_slice(block));
+    m
+}
+
+/// Returns the elements of `arr` that satisfy `pred`, in order.
+pub fn filter<T, F>(arr: &[T], pred: F) -> Vec<T>
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    let n = arr.len();
+    if num_blocks(n, _BLOCK_SIZE * 10) <= 1 {
+        return arr.iter().filter(|x| pred(x)).copied().collect();
+    }
+
+    let (blocks, sums, m) = filter_blocks(arr, pred);
+    let mut dest = maybe_uninit_vec![arr[0]; m];
+    dest
+        .par_ind_chunks_mut(&sums)
+        .zip(blocks.par_iter())
+        .for_each(|(out_chunk, block)| out_chunk.copy_from_slice(block));
+    dest
+}
+
+fn partition_serial_at<T: Copy>(
+    arr: &[T],
+    flags: &[bool],
+    dest_t: &mut [T],
+    dest_f: &mut [T]
+) {
+    let (mut kt, mut kf) = (0, 0);
+    for (x, &f) in arr.iter().zip(flags) {
+        if f { dest_t[kt] = *x; kt += 1; }
+        else { dest_f[kf] = *x; kf += 1; }
+    }
+}
+
+/// Stable partition of `arr`: returns the elements that satisfy `pred`
+/// and the ones that do not, both in their original order.
+pub fn partition<T, F>(arr: &[T], pred: F) -> (Vec<T>, Vec<T>)
+where
+    T: Copy + Send + Sync,
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    let n = arr.len();
+    if n == 0 { return (vec![], vec![]); }
+
+    let flags: Vec<bool> = arr.par_iter().map(&pred).collect();
+    let bls = _BLOCK_SIZE * 10;
+    let mut sums: Vec<usize> = flags
+        .par_chunks(bls)
+        .map(sum_bool_serial)
+        .collect();
+    let m = scan_inplace(&mut sums, false, |a, b| a + b);
+    // block i starts at i * bls, so its first false goes right after the
+    // falses of the previous blocks
+    let sums_f: Vec<usize> = sums
+        .par_iter()
+        .enumerate()
+        .map(|(i, s)| i * bls - s)
+        .collect();
+
+    let mut dest_t = maybe_uninit_vec![arr[0]; m];
+    let mut dest_f = maybe_uninit_vec![arr[0]; n - m];
+    dest_t
+        .par_ind_chunks_mut(&sums)
+        .zip(dest_f.par_ind_chunks_mut(&sums_f))
+        .zip(arr.par_chunks(bls).zip(flags.par_chunks(bls)))
+        .for_each(|((out_t, out_f), (arr_chunk, flag_chunk))| {
+            partition_serial_at(arr_chunk, flag_chunk, out_t, out_f);
+        });
+    (dest_t, dest_f)
+}
+
+/// Returns the index of the first element of `arr` that satisfies `pred`.
+pub fn find_if<T, F>(arr: &[T], pred: F) -> Option<usize>
+where
+    T: Sync,
+    F: Fn(&T) -> bool + Send + Sync,
+{
+    let n = arr.len();
+    let bls = _BLOCK_SIZE;
+    // search prefixes of doubling size so that an early match does not pay
+    // for scanning the whole input
+    let (mut i, mut len) = (0, bls);
+    while i < n {
+        let j = n.min(i + len);
+        let r = arr[i..j]
+            .par_chunks(bls)
+            .enumerate()
+            .filter_map(|(b, chunk)| {
+                chunk.iter().position(&pred).map(|p| i + b * bls + p)
+            }).min();
+        if r.is_some() { return r; }
+        i = j;
+        len *= 2;
+    }
+    None
+}
+
+
+/* -------------------- Tokens -------------------- */
+
+/// Returns the `(start, end)` offsets of the maximal runs of elements of
+/// `arr` that are not separators according to `is_sep` (`end` is exclusive).
+pub fn token_offsets<T, F>(arr: &[T], is_sep: F) -> Vec<(usize, usize)>
+where
+    T: Copy + Sync,
+    F: Fn(T) -> bool + Send + Sync,
+{
+    let n = arr.len();
+    if n == 0 { return vec![]; }
+
+    // flag the first and the last element of each token
+    let (starts_f, ends_f): (Vec<bool>, Vec<bool>) = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let in_token = !is_sep(arr[i]);
+            (
+                in_token && (i == 0 || is_sep(arr[i-1])),
+                in_token && (i + 1 == n || is_sep(arr[i+1]))
+            )
+        }).unzip();
+
+    let (mut starts, mut ends): (Vec<usize>, Vec<usize>) = (vec![], vec![]);
+    pack_index(&starts_f, &mut starts);
+    pack_index(&ends_f, &mut ends);
+    debug_assert_eq!(starts.len(), ends.len());
+
+    starts
+        .into_par_iter()
+        .zip(ends.into_par_iter())
+        .map(|(s, e)| (s, e + 1))
+        .collect()
+}
+
+/// Splits `arr` into the maximal runs of elements that are not separators
+/// according to `is_sep`.
+pub fn tokens<T, F>(arr: &[T], is_sep: F) -> Vec<&[T]>
+where
+    T: Copy + Sync,
+    F: Fn(T) -> bool + Send + Sync,
+{
+    token_offsets(arr, is_sep)
+        .into_par_iter()
+        .map(|(s, e)| &arr[s..e])
+        .collect()
+}
+
+/// Applies `f` to every token of `arr` (see `tokens`) without materializing
+/// the tokens first.
+pub fn map_tokens<T, R, F, G>(arr: &[T], f: F, is_sep: G) -> Vec<R>
+where
+    T: Copy + Sync,
+    R: Send,
+    F: Fn(&[T]) -> R + Send + Sync,
+    G: Fn(T) -> bool + Send + Sync,
+{
+    token_offsets(arr, is_sep)
+        .into_par_iter()
+        .map(|(s, e)| f(&arr[s..e]))
+        .collect()
+}
+
+/// Splits `arr` into contiguous slices, cutting after every position `i`
+/// for which `flags[i]` is true. Always returns `count(flags) + 1` slices.
+pub fn split_at<'a, T: Sync>(arr: &'a [T], flags: &[bool]) -> Vec<&'a [T]> {
+    let n = arr.len();
+    debug_assert_eq!(n, flags.len());
+    let mut ends: Vec<usize> = vec![];
+    if n > 0 { pack_index(flags, &mut ends); }
+
+    let m = ends.len();
+    (0..m+1)
+        .into_par_iter()
+        .map(|i| {
+            let s = if i == 0 { 0 } else { ends[i-1] + 1 };
+            let e = if i == m { n } else { ends[i] + 1 };
+            &arr[s..e]
+        }).collect()
+}
+
 /* -------------------- Flatten -------------------- */
 
 pub fn flatten<T>(arr: &[&Vec<T>], dest: &mut Vec<T>)
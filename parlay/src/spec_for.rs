@@ -0,0 +1,444 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+//! Deterministic reservations: a speculative parallel loop that gives the
+//! same result as running its iterations sequentially in order.
+//!
+//! Each round runs `reserve` on a prefix of the remaining iterations, then
+//! `commit` on the ones that reserved successfully. Iterations that fail to
+//! commit are retried in the next round, ahead of the new ones. Iterations
+//! usually reserve shared resources with a [`Reservation`] by their index, so
+//! that the earliest iteration wins.
+
+use std::fmt;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+use std::cmp::{min, max};
+use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+use rayon::prelude::*;
+use enhanced_rayon::prelude::*;
+
+use crate::maybe_uninit_vec;
+use crate::primitives::pack;
+use crate::utilities::write_min;
+
+
+/// A priority write cell: keeps the smallest index reserved on it.
+pub struct Reservation(AtomicU32);
+
+impl Reservation {
+    const MAX_IDX: u32 = u32::MAX;
+
+    pub fn new() -> Self {
+        Self(AtomicU32::new(Self::MAX_IDX))
+    }
+
+    pub const fn max_idx() -> u32 {
+        u32::MAX
+    }
+
+    pub fn get(&self) -> u32 {
+        self.0.load(Relaxed)
+    }
+
+    pub fn reserve(&self, i: u32) -> bool {
+        write_min(&self.0, i)
+    }
+
+    pub fn reserved(&self) -> bool {
+        self.0.load(Relaxed) < Self::MAX_IDX
+    }
+
+    pub fn reset(&self) {
+        self.0.store(Self::MAX_IDX, Relaxed)
+    }
+
+    pub fn check(&self, i: u32) -> bool {
+        self.0.load(Relaxed) == i
+    }
+
+    pub fn check_reset(&self, i: u32) -> bool {
+        if self.0.load(Relaxed) == i {
+            self.0.store(Self::MAX_IDX, Relaxed); true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for Reservation {
+    fn default() -> Self { Self::new() }
+}
+
+/// The iterations of a speculative loop, in priority order.
+pub trait SpecForItems: Sync {
+    type Item: Copy + Send + Sync;
+
+    fn len(&self) -> usize;
+    fn item(&self, i: usize) -> Self::Item;
+
+    fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+impl SpecForItems for Range<usize> {
+    type Item = usize;
+
+    fn len(&self) -> usize { ExactSizeIterator::len(self) }
+    fn item(&self, i: usize) -> usize { self.start + i }
+}
+
+impl<T: Copy + Send + Sync> SpecForItems for [T] {
+    type Item = T;
+
+    fn len(&self) -> usize { <[T]>::len(self) }
+    fn item(&self, i: usize) -> T { self[i] }
+}
+
+impl<T: Copy + Send + Sync> SpecForItems for Vec<T> {
+    type Item = T;
+
+    fn len(&self) -> usize { Vec::len(self) }
+    fn item(&self, i: usize) -> T { self[i] }
+}
+
+/// Details of a round, collected with `SpecFor::detailed_stats`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundStats {
+    /// the number of iterations whose `reserve` returned true
+    pub reserved: usize,
+    /// the number of iterations whose `commit` returned true
+    pub committed: usize,
+    pub reserve_time: Duration,
+    pub commit_time: Duration,
+}
+
+/// Statistics of a speculative loop, one entry per round.
+#[derive(Debug, Clone, Default)]
+pub struct SpecForStats {
+    /// the number of iterations attempted in each round
+    pub round_sizes: Vec<usize>,
+    /// the number of iterations that failed to commit in each round
+    pub round_aborts: Vec<usize>,
+    /// empty unless `SpecFor::detailed_stats` is set
+    pub details: Vec<RoundStats>,
+}
+
+impl SpecForStats {
+    pub fn rounds(&self) -> usize { self.round_sizes.len() }
+
+    pub fn total_processed(&self) -> usize { self.round_sizes.iter().sum() }
+
+    pub fn total_aborts(&self) -> usize { self.round_aborts.iter().sum() }
+
+    /// the fraction of the attempted iterations that had to be retried
+    pub fn abort_ratio(&self) -> f64 {
+        let p = self.total_processed();
+        if p == 0 { 0.0 } else { self.total_aborts() as f64 / p as f64 }
+    }
+}
+
+/// `{:#}` also prints one line per round.
+impl fmt::Display for SpecForStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "rounds: {}, processed: {}, aborts: {}, abort ratio: {:.4}",
+            self.rounds(),
+            self.total_processed(),
+            self.total_aborts(),
+            self.abort_ratio()
+        )?;
+        if !f.alternate() { return Ok(()); }
+
+        if self.details.is_empty() {
+            write!(f, "\nround\tsize\taborts")?;
+        } else {
+            write!(
+                f,
+                "\nround\tsize\taborts\treserved\tcommitted\treserve_t\tcommit_t"
+            )?;
+        }
+        for i in 0..self.rounds() {
+            write!(f, "\n{i}\t{}\t{}", self.round_sizes[i], self.round_aborts[i])?;
+            if let Some(d) = self.details.get(i) {
+                write!(
+                    f,
+                    "\t{}\t{}\t{:?}\t{:?}",
+                    d.reserved,
+                    d.committed,
+                    d.reserve_time,
+                    d.commit_time
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecForError {
+    /// a round committed none of its iterations; the earliest remaining
+    /// iteration should always succeed, so `reserve`/`commit` are broken.
+    Stalled { round: usize, remaining: usize },
+    /// the loop did not finish within the configured number of rounds.
+    TooManyRounds { rounds: usize, remaining: usize },
+}
+
+impl fmt::Display for SpecForError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stalled { round, remaining } => write!(
+                f,
+                "speculative for stalled in round {round} \
+                with {remaining} iterations remaining"
+            ),
+            Self::TooManyRounds { rounds, remaining } => write!(
+                f,
+                "speculative for did not finish in {rounds} rounds \
+                ({remaining} iterations remaining)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpecForError {}
+
+/// Configuration of a speculative loop.
+///
+/// ```ignore
+/// let stats = SpecFor::new()
+///     .granularity(20)
+///     .reserve_chunk(1024)
+///     .commit_chunk(2048)
+///     .run(&(0..n), reserve, commit)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpecFor {
+    granularity: usize,
+    reserve_chunk: Option<usize>,
+    commit_chunk: Option<usize>,
+    max_rounds: Option<usize>,
+    detailed_stats: bool,
+}
+
+impl Default for SpecFor {
+    fn default() -> Self { Self::new() }
+}
+
+impl SpecFor {
+    pub fn new() -> Self {
+        Self {
+            granularity: 20,
+            reserve_chunk: None,
+            commit_chunk: None,
+            max_rounds: None,
+            detailed_stats: false
+        }
+    }
+
+    /// rounds have at most `1/granularity` of the iterations (default 20).
+    pub fn granularity(mut self, granularity: usize) -> Self {
+        assert!(granularity > 0, "granularity must be positive");
+        self.granularity = granularity;
+        self
+    }
+
+    /// the rayon chunk size of the reserve phase (default: granularity).
+    pub fn reserve_chunk(mut self, size: usize) -> Self {
+        self.reserve_chunk = Some(size);
+        self
+    }
+
+    /// the rayon chunk size of the commit phase (default: granularity).
+    pub fn commit_chunk(mut self, size: usize) -> Self {
+        self.commit_chunk = Some(size);
+        self
+    }
+
+    /// fails with `TooManyRounds` after `rounds` rounds (default: no limit).
+    pub fn max_rounds(mut self, rounds: usize) -> Self {
+        self.max_rounds = Some(rounds);
+        self
+    }
+
+    /// also collects the success counts and the timing of each round.
+    pub fn detailed_stats(mut self, on: bool) -> Self {
+        self.detailed_stats = on;
+        self
+    }
+
+    /// Runs `reserve` and `commit` over `items`. `reserve` returns false if
+    /// the iteration has nothing to do, `commit` returns false if the
+    /// iteration lost its reservations and must be retried.
+    pub fn run<It, R, C>(
+        &self,
+        items: &It,
+        reserve: R,
+        commit: C
+    ) -> Result<SpecForStats, SpecForError> where
+        It: SpecForItems + ?Sized,
+        R: Fn(It::Item) -> bool + Send + Sync,
+        C: Fn(It::Item) -> bool + Send + Sync,
+    {
+        self.run_stateful(
+            items,
+            |i, _: &mut ()| reserve(i),
+            |i, _: &mut ()| commit(i),
+            ()
+        )
+    }
+
+    /// Same as `run`, but collects the iterations of `items` first.
+    pub fn run_iter<I, T, R, C>(
+        &self,
+        items: I,
+        reserve: R,
+        commit: C
+    ) -> Result<SpecForStats, SpecForError> where
+        I: IntoIterator<Item = T>,
+        T: Copy + Send + Sync,
+        R: Fn(T) -> bool + Send + Sync,
+        C: Fn(T) -> bool + Send + Sync,
+    {
+        let items: Vec<T> = items.into_iter().collect();
+        self.run(&items, reserve, commit)
+    }
+
+    /// Same as `run`, but every iteration gets a copy of `state` that is
+    /// passed from its `reserve` to its `commit` in the same round.
+    pub fn run_stateful<It, S, R, C>(
+        &self,
+        items: &It,
+        reserve: R,
+        commit: C,
+        state: S
+    ) -> Result<SpecForStats, SpecForError> where
+        It: SpecForItems + ?Sized,
+        S: Clone + Send + Sync,
+        R: Fn(It::Item, &mut S) -> bool + Send + Sync,
+        C: Fn(It::Item, &mut S) -> bool + Send + Sync,
+    {
+        // initialization:
+        let n = items.len();
+        let max_round_size = n / self.granularity + 1;
+        let mut current_round_size = max(1, max_round_size / 4);
+        let (mut number_keep, mut number_done) = (0usize, 0usize);
+        let r_chunk_size = self.reserve_chunk.unwrap_or(self.granularity);
+        let c_chunk_size = self.commit_chunk.unwrap_or(self.granularity);
+        let mut stats = SpecForStats::default();
+
+        let mut i_hold = Vec::<usize>::new();
+        let mut idxs = maybe_uninit_vec![0usize; max_round_size];
+        let mut keep = maybe_uninit_vec![false; max_round_size];
+        let mut st = vec![state; max_round_size];
+
+        // main loop:
+        while number_done < n {
+            let round = stats.rounds();
+            if self.max_rounds.is_some_and(|m| round >= m) {
+                return Err(SpecForError::TooManyRounds {
+                    rounds: round,
+                    remaining: n - number_done
+                });
+            }
+            let size = min(current_round_size, n - number_done);
+            let timer = Instant::now();
+
+            // reserve
+            (
+                &mut idxs[..size],
+                &mut keep[..size],
+                &mut st[..size]
+            )
+                .into_par_iter()
+                .with_gran(r_chunk_size)
+                .enumerate()
+                .for_each(|(i, (ii, ki, si))| {
+                    *ii = if i < number_keep { i_hold[i] }
+                        else { number_done + i };
+                    *ki = reserve(items.item(*ii), si);
+                });
+
+            let (mut reserved, mut reserve_time) = (0, Duration::ZERO);
+            if self.detailed_stats {
+                reserve_time = timer.elapsed();
+                reserved = keep[..size].par_iter().filter(|&&k| k).count();
+            }
+            let timer = Instant::now();
+
+            // commit
+            (
+                &mut keep[..size],
+                &mut st[..size]
+            )
+                .into_par_iter()
+                .with_gran(c_chunk_size)
+                .enumerate()
+                .for_each(|(i, (ki, si))| {
+                    if *ki { *ki = !commit(items.item(idxs[i]), si); }
+                });
+
+            let commit_time = timer.elapsed();
+
+            // keep iterations that failed for next round
+            pack(&idxs[..size], &keep[..size], &mut i_hold);
+            number_keep = i_hold.len();
+            number_done = number_done + size - number_keep;
+            stats.round_sizes.push(size);
+            stats.round_aborts.push(number_keep);
+            if self.detailed_stats {
+                stats.details.push(RoundStats {
+                    reserved,
+                    committed: reserved - number_keep,
+                    reserve_time,
+                    commit_time
+                });
+            }
+
+            if number_keep == size {
+                return Err(SpecForError::Stalled {
+                    round,
+                    remaining: n - number_done
+                });
+            }
+
+            // adjust round size based on the number of failed attempts
+            if (number_keep as f32 / size as f32) > 0.2 {
+                current_round_size = max(
+                    current_round_size / 2,
+                    max(max_round_size/64 + 1, number_keep)
+                );
+            } else if (number_keep as f32 / size as f32) < 0.1 {
+                current_round_size = min(
+                    current_round_size * 2,
+                    max_round_size
+                );
+            }
+        }
+
+        Ok(stats)
+    }
+}
@@ -24,6 +24,7 @@
 // SOFTWARE.
 // ============================================================================
 
+use std::mem::MaybeUninit;
 use std::sync::atomic::{AtomicU32, Ordering};
 use num_traits::PrimInt;
 
@@ -65,6 +66,17 @@ pub fn hash64_cheap(mut x: u64) -> u64
     x
 }
 
+/// calculates a hash of a byte string by combining `hash64` of its words.
+pub fn hash_bytes(s: &[u8]) -> u64 {
+    s
+        .chunks(8)
+        .fold(s.len() as u64, |h, c| {
+            let mut w = [0u8; 8];
+            w[..c.len()].copy_from_slice(c);
+            hash64(h ^ u64::from_le_bytes(w))
+        })
+}
+
 /// tries to write b to a atomically while b is smaller than a.
 /// returns true if successful and false otherwise.
 #[inline(always)]
@@ -82,4 +94,43 @@ pub fn write_min(a: &AtomicU32, b: u32) -> bool {
         }
     }
     false
-}
\ No newline at end of file
+}
+
+/// Aborts the process if dropped while unwinding. Used by the code that
+/// moves elements around with bitwise copies, where a panic half way
+/// through would leave some of the elements duplicated.
+pub(crate) struct AbortOnPanic;
+
+impl Drop for AbortOnPanic {
+    fn drop(&mut self) {
+        if std::thread::panicking() { std::process::abort(); }
+    }
+}
+
+/// Allocates `n` uninitialized slots to be used as scratch space by the
+/// code that moves elements around with bitwise copies.
+pub(crate) fn uninit_buffer<T>(n: usize) -> Vec<MaybeUninit<T>> {
+    let mut buf = Vec::with_capacity(n);
+    // SAFETY: `MaybeUninit` does not need to be initialized
+    unsafe { buf.set_len(n); }
+    buf
+}
+
+/// Views an initialized slice as uninitialized slots. The caller must not
+/// write uninitialized values through the returned slice.
+#[inline(always)]
+pub(crate) unsafe fn as_uninit_mut<T>(s: &mut [T]) -> &mut [MaybeUninit<T>] {
+    &mut *(s as *mut [T] as *mut [MaybeUninit<T>])
+}
+
+/// Views slots as initialized elements. All of them must have been written.
+#[inline(always)]
+pub(crate) unsafe fn assume_init_mut<T>(s: &mut [MaybeUninit<T>]) -> &mut [T] {
+    &mut *(s as *mut [MaybeUninit<T>] as *mut [T])
+}
+
+/// Immutable version of `assume_init_mut`.
+#[inline(always)]
+pub(crate) unsafe fn assume_init_ref<T>(s: &[MaybeUninit<T>]) -> &[T] {
+    &*(s as *const [MaybeUninit<T>] as *const [T])
+}
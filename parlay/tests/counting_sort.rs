@@ -0,0 +1,39 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::counting_sort::seq_count_sort;
+
+
+#[test]
+fn seq_count_sort_stable() {
+    let inp: Vec<(u32, usize)> = (0..20).map(|i| ((i * 7 % 3) as u32, i)).collect();
+    let keys: Vec<u32> = inp.iter().map(|p| p.0).collect();
+    let mut out = vec![(0, 0); inp.len()];
+    seq_count_sort(&inp, &mut out, &keys, 3);
+    let mut expected = inp.clone();
+    expected.sort_by_key(|p| p.0);
+    assert_eq!(out, expected);
+}
@@ -0,0 +1,92 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::primitives::{filter, filter_into, find_if, partition};
+use parlay::utilities::hash64;
+
+
+fn input(n: usize) -> Vec<u64> {
+    (0..n as u64).map(|i| hash64(i) % 100).collect()
+}
+
+#[test]
+fn filter_matches_serial() {
+    for n in [0, 1, 1000, 100_000] {
+        let inp = input(n);
+        let pred = |x: &u64| x.is_multiple_of(3);
+        let expected: Vec<u64> = inp.iter().copied().filter(pred).collect();
+        assert_eq!(filter(&inp, pred), expected);
+
+        let mut dest = vec![0; n];
+        let m = filter_into(&inp, pred, &mut dest);
+        assert_eq!(&dest[..m], &expected[..]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "destination is too small")]
+fn filter_into_short_dest_serial() {
+    let inp = input(100);
+    let mut dest = vec![0; 1];
+    filter_into(&inp, |_| true, &mut dest);
+}
+
+#[test]
+#[should_panic(expected = "destination is too small")]
+fn filter_into_short_dest_parallel() {
+    let inp = input(100_000);
+    let mut dest = vec![0; 1];
+    filter_into(&inp, |_| true, &mut dest);
+}
+
+#[test]
+fn partition_is_stable() {
+    for n in [0, 1, 1000, 100_000] {
+        // tag every element with its position to check stability
+        let inp: Vec<(u64, usize)> = input(n).into_iter().zip(0..).collect();
+        let pred = |x: &(u64, usize)| x.0 < 30;
+        let (t, f) = partition(&inp, pred);
+        let expected_t: Vec<_> = inp.iter().copied().filter(pred).collect();
+        let expected_f: Vec<_> = inp.iter().copied().filter(|x| !pred(x)).collect();
+        assert_eq!(t, expected_t);
+        assert_eq!(f, expected_f);
+    }
+}
+
+#[test]
+fn find_if_returns_first() {
+    let n = 100_000;
+    let inp = input(n);
+    for v in [0, 50, 99, 100] {
+        let expected = inp.iter().position(|&x| x == v);
+        assert_eq!(find_if(&inp, |&x| x == v), expected);
+    }
+    // matches only near the end, past several doubling rounds
+    let mut late = vec![0u64; n];
+    late[n - 2] = 1; late[n - 1] = 1;
+    assert_eq!(find_if(&late, |&x| x == 1), Some(n - 2));
+    assert_eq!(find_if(&[] as &[u64], |_| true), None);
+}
@@ -0,0 +1,99 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::integer_sort::{integer_sort, OrderedKey};
+use parlay::utilities::hash64;
+
+
+fn sort_by_key<T: OrderedKey + Send + Sync>(arr: &[T]) -> Vec<T> {
+    let mut out = vec![];
+    integer_sort(arr, &|x: T| x.ordered_key(), 0, &mut out);
+    out
+}
+
+#[test]
+fn signed_keys_keep_their_order() {
+    let vals = [i32::MIN, -7, -1, 0, 1, 7, i32::MAX];
+    for w in vals.windows(2) {
+        assert!(w[0].ordered_key() < w[1].ordered_key());
+    }
+    let vals = [i64::MIN, -1, 0, i64::MAX];
+    for w in vals.windows(2) {
+        assert!(w[0].ordered_key() < w[1].ordered_key());
+    }
+}
+
+#[test]
+fn float_keys_follow_total_cmp() {
+    let vals = [
+        -f64::NAN, f64::NEG_INFINITY, -1e300, -1.5, -f64::MIN_POSITIVE,
+        -0.0, 0.0, f64::MIN_POSITIVE, 2.25, f64::INFINITY, f64::NAN,
+    ];
+    for a in vals {
+        for b in vals {
+            assert_eq!(
+                a.ordered_key().cmp(&b.ordered_key()),
+                a.total_cmp(&b),
+                "{a} vs {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn sorts_negative_integers() {
+    let n = 100_000;
+    let arr: Vec<i64> = (0..n)
+        .map(|i| hash64(i) as i64 % 1000 - 500)
+        .collect();
+    let mut exp = arr.clone();
+    exp.sort();
+    assert_eq!(sort_by_key(&arr), exp);
+
+    let arr: Vec<i32> = (0..n).map(|i| hash64(i) as i32).collect();
+    let mut exp = arr.clone();
+    exp.sort();
+    assert_eq!(sort_by_key(&arr), exp);
+}
+
+#[test]
+fn sorts_doubles_with_zeros_and_nans() {
+    let n = 100_000;
+    let arr: Vec<f64> = (0..n)
+        .map(|i| match hash64(i) % 8 {
+            0 => -0.0,
+            1 => 0.0,
+            2 => f64::NAN,
+            3 => f64::NEG_INFINITY,
+            _ => (hash64(i + n) % 2000) as f64 / 8.0 - 125.0,
+        })
+        .collect();
+    let mut exp = arr.clone();
+    exp.sort_by(f64::total_cmp);
+    let r = sort_by_key(&arr);
+    let bits = |v: &[f64]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+    assert_eq!(bits(&r), bits(&exp));
+}
@@ -0,0 +1,85 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::merge::{kway_merge, merge};
+use parlay::utilities::hash64;
+
+
+// a sorted run of keys with duplicates, tagged with (run, position)
+fn run(r: usize, n: usize) -> Vec<(u64, usize, usize)> {
+    let mut keys: Vec<u64> = (0..n).map(|i| hash64((r * n + i) as u64) % 50).collect();
+    keys.sort();
+    keys.into_iter().enumerate().map(|(i, k)| (k, r, i)).collect()
+}
+
+fn less(a: (u64, usize, usize), b: (u64, usize, usize)) -> bool { a.0 < b.0 }
+
+// a stable sort by key of the concatenated runs is a stable merge
+fn oracle(runs: &[Vec<(u64, usize, usize)>]) -> Vec<(u64, usize, usize)> {
+    let mut expected: Vec<_> = runs.concat();
+    expected.sort_by_key(|x| x.0);
+    expected
+}
+
+#[test]
+fn merge_is_stable() {
+    for (n1, n2) in [
+        (0, 0), (0, 10), (10, 0), (0, 30_000), (30_000, 0),
+        (1000, 3000), (50_000, 20_000)
+    ] {
+        let runs = [run(0, n1), run(1, n2)];
+        let mut out = vec![(0, 0, 0); n1 + n2];
+        merge(&runs[0], &runs[1], &mut out, less);
+        assert_eq!(out, oracle(&runs));
+    }
+}
+
+#[test]
+#[should_panic(expected = "wrong output size")]
+fn merge_wrong_size() {
+    let a = run(0, 10);
+    let mut out = vec![(0, 0, 0); 5];
+    merge(&a, &a, &mut out, less);
+}
+
+#[test]
+fn kway_merge_is_stable() {
+    let sizes: [&[usize]; 6] = [
+        &[],
+        &[1000],
+        &[0, 0, 0],
+        &[0, 500, 0, 700, 0],
+        &[300, 1, 0, 2000, 17, 900, 40],
+        &[20_000, 0, 30_000, 10_000],
+    ];
+    for s in sizes {
+        let runs: Vec<_> = s.iter().enumerate().map(|(r, &n)| run(r, n)).collect();
+        let refs: Vec<&[_]> = runs.iter().map(|r| &r[..]).collect();
+        let mut out = vec![(0, 0, 0); s.iter().sum()];
+        kway_merge(&refs, &mut out, less);
+        assert_eq!(out, oracle(&runs), "run sizes {:?}", s);
+    }
+}
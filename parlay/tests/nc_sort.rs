@@ -0,0 +1,93 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::{
+    nc_bucket_sort, nc_merge_sort_inplace, nc_quick_sort, nc_sample_sort_inplace
+};
+use parlay::utilities::hash64;
+
+
+// owned strings with many duplicates, tagged with their input position
+fn strings(n: usize) -> Vec<(String, usize)> {
+    (0..n).map(|i| (format!("k{}", hash64(i as u64) % 1000), i)).collect()
+}
+
+fn by_key(a: &(String, usize), b: &(String, usize)) -> bool { a.0 < b.0 }
+
+fn sorted_keys(v: &[(String, usize)]) -> Vec<String> {
+    let mut keys: Vec<String> = v.iter().map(|p| p.0.clone()).collect();
+    keys.sort();
+    keys
+}
+
+fn stable_oracle(v: &[(String, usize)]) -> Vec<(String, usize)> {
+    let mut expected = v.to_vec();
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    expected
+}
+
+#[test]
+fn nc_sorts_stable() {
+    for n in [0, 1, 47, 1000, 100_000] {
+        let inp = strings(n);
+        let expected = stable_oracle(&inp);
+
+        let mut out = inp.clone();
+        nc_merge_sort_inplace(&mut out, by_key);
+        assert_eq!(out, expected, "merge sort, n = {}", n);
+
+        let mut out = inp.clone();
+        nc_sample_sort_inplace(&mut out, by_key, true);
+        assert_eq!(out, expected, "sample sort, n = {}", n);
+
+        let mut out = inp.clone();
+        nc_bucket_sort(&mut out, by_key, true);
+        assert_eq!(out, expected, "bucket sort, n = {}", n);
+    }
+}
+
+#[test]
+fn nc_sorts_unstable() {
+    for n in [0, 1, 47, 1000, 100_000] {
+        let inp = strings(n);
+        let expected = sorted_keys(&inp);
+        let keys = |v: Vec<(String, usize)>| -> Vec<String> {
+            v.into_iter().map(|p| p.0).collect()
+        };
+
+        let mut out = inp.clone();
+        nc_quick_sort(&mut out, by_key);
+        assert_eq!(keys(out), expected, "quick sort, n = {}", n);
+
+        let mut out = inp.clone();
+        nc_sample_sort_inplace(&mut out, by_key, false);
+        assert_eq!(keys(out), expected, "sample sort, n = {}", n);
+
+        let mut out = inp.clone();
+        nc_bucket_sort(&mut out, by_key, false);
+        assert_eq!(keys(out), expected, "bucket sort, n = {}", n);
+    }
+}
@@ -0,0 +1,82 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::collections::HashMap;
+
+use parlay::internal::group_by::reduce_by_key;
+use parlay::utilities::hash64;
+
+
+fn oracle<F: Fn(u64, u64) -> u64>(inp: &[(u64, u64)], id: u64, f: F) -> Vec<(u64, u64)> {
+    let mut m: HashMap<u64, u64> = HashMap::new();
+    for &(k, v) in inp {
+        let e = m.entry(k).or_insert(id);
+        *e = f(*e, v);
+    }
+    let mut r: Vec<_> = m.into_iter().collect();
+    r.sort();
+    r
+}
+
+fn check<F>(inp: &[(u64, u64)], id: u64, f: F)
+where
+    F: Fn(u64, u64) -> u64 + Send + Sync + Copy,
+{
+    let mut r = reduce_by_key(inp, |(k, _)| k, |(_, v)| v, id, f);
+    r.sort();
+    assert_eq!(r, oracle(inp, id, f));
+}
+
+#[test]
+fn reduce_by_key_dense() {
+    // keys smaller than n take the dense path
+    for (n, keys) in [(1, 1), (1000, 10), (100_000, 50_000)] {
+        let inp: Vec<(u64, u64)> = (0..n as u64)
+            .map(|i| (hash64(i) % keys, hash64(i + n as u64) % 1000))
+            .collect();
+        check(&inp, 0, |a, b| a + b);
+        check(&inp, 0, |a, b| a.max(b));
+        check(&inp, u64::MAX, |a, b| a.min(b));
+    }
+}
+
+#[test]
+fn reduce_by_key_sparse() {
+    // large keys are hashed
+    for (n, keys) in [(1, 1), (1000, 10), (100_000, 50_000)] {
+        let inp: Vec<(u64, u64)> = (0..n as u64)
+            .map(|i| ((hash64(i) % keys) << 40, hash64(i + n as u64) % 1000))
+            .collect();
+        check(&inp, 0, |a, b| a + b);
+        check(&inp, u64::MAX, |a, b| a.min(b));
+    }
+}
+
+#[test]
+fn reduce_by_key_empty() {
+    let inp: Vec<(u64, u64)> = vec![];
+    assert!(reduce_by_key(&inp, |(k, _)| k, |(_, v)| v, 0, |a, b| a + b).is_empty());
+}
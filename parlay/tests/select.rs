@@ -0,0 +1,84 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use parlay::internal::{kth_smallest, median, top_k};
+use parlay::utilities::hash64;
+
+
+// many duplicates, and big enough to take the parallel rounds
+fn input(n: usize, range: u64) -> Vec<u64> {
+    (0..n as u64).map(|i| hash64(i) % range).collect()
+}
+
+fn sorted(arr: &[u64]) -> Vec<u64> {
+    let mut s = arr.to_vec();
+    s.sort();
+    s
+}
+
+#[test]
+fn kth_smallest_matches_sort() {
+    for (n, range) in [(1, 1), (100, 3), (100_000, 7), (100_000, 1 << 40)] {
+        let inp = input(n, range);
+        let s = sorted(&inp);
+        for k in [0, 1, n / 3, n / 2, n.saturating_sub(2), n - 1] {
+            if k >= n { continue; }
+            assert_eq!(kth_smallest(&inp, k, |a, b| a < b), s[k], "n = {}, k = {}", n, k);
+        }
+    }
+}
+
+#[test]
+fn kth_smallest_all_equal() {
+    let inp = vec![5u64; 100_000];
+    assert_eq!(kth_smallest(&inp, 0, |a, b| a < b), 5);
+    assert_eq!(kth_smallest(&inp, 99_999, |a, b| a < b), 5);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn kth_smallest_out_of_range() {
+    kth_smallest(&[1u64, 2, 3], 3, |a, b| a < b);
+}
+
+#[test]
+fn top_k_matches_sort() {
+    for (n, range) in [(0, 1), (100, 3), (100_000, 7), (100_000, 1 << 40)] {
+        let inp = input(n, range);
+        let s = sorted(&inp);
+        for k in [0, 1, n / 2, n.saturating_sub(1), n, n + 5] {
+            assert_eq!(top_k(&inp, k, |a, b| a < b), s[..k.min(n)], "n = {}, k = {}", n, k);
+        }
+    }
+}
+
+#[test]
+fn median_is_lower_median() {
+    for n in [1, 2, 101, 100_000] {
+        let inp = input(n, 1000);
+        assert_eq!(median(&inp, |a, b| a < b), sorted(&inp)[(n - 1) / 2]);
+    }
+}
@@ -0,0 +1,79 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::collections::HashMap;
+
+use parlay::internal::group_by::{group_by_key, semisort_inplace};
+use parlay::utilities::hash64;
+
+
+fn pairs(n: usize, keys: u64) -> Vec<(u64, usize)> {
+    (0..n).map(|i| (hash64(i as u64) % keys, i)).collect()
+}
+
+fn hash(k: u64) -> usize { hash64(k) as usize }
+
+// the values of every key, in input order
+fn oracle(inp: &[(u64, usize)]) -> HashMap<u64, Vec<usize>> {
+    let mut groups: HashMap<u64, Vec<usize>> = HashMap::new();
+    for &(k, v) in inp { groups.entry(k).or_default().push(v); }
+    groups
+}
+
+#[test]
+fn semisort_groups_keys() {
+    // few keys give heavy buckets, many keys give light ones
+    for (n, keys) in [(0, 1), (1, 1), (1000, 10), (100_000, 5), (100_000, 50_000)] {
+        let inp = pairs(n, keys);
+        let mut arr = inp.clone();
+        semisort_inplace(&mut arr, |(k, _)| k, hash);
+
+        let mut seen: HashMap<u64, usize> = HashMap::new();
+        for i in 0..n {
+            if i == 0 || arr[i].0 != arr[i-1].0 {
+                assert!(seen.insert(arr[i].0, i).is_none(), "key split in two groups");
+            }
+        }
+        let mut sorted = arr.clone();
+        sorted.sort();
+        let mut expected = inp.clone();
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+}
+
+#[test]
+fn group_by_key_matches_serial() {
+    for (n, keys) in [(0, 1), (1, 1), (1000, 10), (100_000, 5), (100_000, 50_000)] {
+        let inp = pairs(n, keys);
+        let groups = group_by_key(&inp, hash);
+        let expected = oracle(&inp);
+        assert_eq!(groups.len(), expected.len());
+        for (k, vs) in groups {
+            assert_eq!(Some(&vs), expected.get(&k), "key {}", k);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+
+use parlay::spec_for::{Reservation, SpecFor, SpecForError};
+use parlay::utilities::hash64;
+
+
+fn edges(n: usize, m: usize) -> Vec<(usize, usize)> {
+    (0..n as u64)
+        .map(|i| {
+            let u = hash64(2 * i) as usize % m;
+            let v = hash64(2 * i + 1) as usize % (m - 1);
+            (u, if v >= u { v + 1 } else { v })
+        }).collect()
+}
+
+// the greedy maximal matching that takes the edges in order
+fn serial_matching(es: &[(usize, usize)], m: usize) -> Vec<bool> {
+    let mut matched = vec![false; m];
+    es
+        .iter()
+        .map(|&(u, v)| {
+            let take = !matched[u] && !matched[v];
+            if take { matched[u] = true; matched[v] = true; }
+            take
+        }).collect()
+}
+
+#[test]
+fn spec_for_is_deterministic() {
+    let (n, m) = (200_000, 50_000);
+    let es = edges(n, m);
+    let r: Vec<Reservation> = (0..m).map(|_| Reservation::new()).collect();
+    let matched: Vec<AtomicBool> = (0..m).map(|_| AtomicBool::new(false)).collect();
+    let taken: Vec<AtomicBool> = (0..n).map(|_| AtomicBool::new(false)).collect();
+
+    let reserve = |i: usize| {
+        let (u, v) = es[i];
+        if matched[u].load(Relaxed) || matched[v].load(Relaxed) { return false; }
+        r[u].reserve(i as u32);
+        r[v].reserve(i as u32);
+        true
+    };
+    let commit = |i: usize| {
+        let (u, v) = es[i];
+        if r[u].check_reset(i as u32) {
+            if r[v].check_reset(i as u32) {
+                matched[u].store(true, Relaxed);
+                matched[v].store(true, Relaxed);
+                taken[i].store(true, Relaxed);
+                return true;
+            }
+        } else {
+            r[v].check_reset(i as u32);
+        }
+        false
+    };
+
+    let stats = SpecFor::new()
+        .detailed_stats(true)
+        .run(&(0..n), reserve, commit)
+        .unwrap();
+
+    let taken: Vec<bool> = taken.iter().map(|t| t.load(Relaxed)).collect();
+    assert_eq!(taken, serial_matching(&es, m));
+    assert_eq!(stats.total_processed() - stats.total_aborts(), n);
+    assert_eq!(stats.details.len(), stats.rounds());
+}
+
+#[test]
+fn spec_for_empty() {
+    let stats = SpecFor::new().run(&(0..0), |_| true, |_| true).unwrap();
+    assert_eq!(stats.rounds(), 0);
+}
+
+#[test]
+fn spec_for_stalled() {
+    let n = 1000;
+    let res = SpecFor::new().run(&(0..n), |_| true, |_| false);
+    assert_eq!(res.unwrap_err(), SpecForError::Stalled { round: 0, remaining: n });
+}
+
+#[test]
+fn spec_for_too_many_rounds() {
+    let n = 1000;
+    let res = SpecFor::new().max_rounds(1).run(&(0..n), |_| true, |_| true);
+    match res {
+        Err(SpecForError::TooManyRounds { rounds: 1, remaining }) => {
+            assert!(remaining > 0 && remaining < n);
+        },
+        r => panic!("unexpected result {:?}", r),
+    }
+}
+
+#[test]
+fn spec_for_stateful() {
+    // the state written by reserve reaches the commit of the same iteration
+    let items: Vec<u64> = (0..10_000).collect();
+    let sum = std::sync::atomic::AtomicU64::new(0);
+    SpecFor::new()
+        .run_stateful(
+            &items,
+            |x, s: &mut u64| { *s = 2 * x; true },
+            |x, s: &mut u64| { assert_eq!(*s, 2 * x); sum.fetch_add(*s, Relaxed); true },
+            0
+        ).unwrap();
+    assert_eq!(sum.load(Relaxed), items.iter().map(|x| 2 * x).sum());
+}
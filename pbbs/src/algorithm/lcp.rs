@@ -29,11 +29,11 @@ use enhanced_rayon::prelude::*;
 
 use parlay::{Timer, maybe_uninit_vec};
 use parlay::primitives::pack_index;
-use crate::{DefChar, DefInt, DefAtomInt, ORDER};
+use crate::{DefInt, DefAtomInt, ORDER};
 use crate::range_min::{RangeMin, AtomU32RangeMin};
 
 #[allow(dead_code)]
-pub fn lcp(s: &[DefChar], sa: &[DefInt]) -> Vec<DefInt> {
+pub fn lcp<T: PartialEq + Sync>(s: &[T], sa: &[DefInt]) -> Vec<DefInt> {
     let mut t = Timer::new("lcp"); //t.start();
     let mut len = 111;
     let n = sa.len();
@@ -114,7 +114,10 @@ pub fn lcp(s: &[DefChar], sa: &[DefInt]) -> Vec<DefInt> {
 
 
 #[allow(dead_code)]
-pub fn atomic_lcp(s: &[DefChar], sa: &[DefAtomInt]) -> Vec<DefAtomInt> {
+pub fn atomic_lcp<T: PartialEq + Sync>(
+    s: &[T],
+    sa: &[DefAtomInt]
+) -> Vec<DefAtomInt> {
     let mut t = Timer::new("lcp"); //t.start();
     let mut len = 111;
     let n = sa.len();
@@ -29,7 +29,7 @@ use std::slice::from_raw_parts_mut;
 use rayon::prelude::*;
 use enhanced_rayon::prelude::*;
 
-use crate::{DefInt, DefChar, DefAtomInt, ORDER};
+use crate::{DefInt, DefAtomInt, ORDER};
 use parlay::internal::sequence_ops::scan_inplace;
 use parlay::{Timer, maybe_uninit_vec};
 
@@ -38,6 +38,114 @@ use parlay::{Timer, maybe_uninit_vec};
 struct Seg { s: DefInt, l: DefInt }
 
 
+/// A symbol of the strings whose suffix arrays we build. Small alphabets are
+/// renumbered with a table indexed by the symbols, larger ones by sorting
+/// the distinct symbols that occur.
+pub trait Symbol: Copy + Ord + Send + Sync {
+    /// the number of entries in the table, 0 to sort instead
+    const TABLE_SIZE: usize = 0;
+
+    /// the symbol's entry in the table
+    fn table_index(self) -> usize { 0 }
+}
+
+impl Symbol for u8 {
+    const TABLE_SIZE: usize = 1 << 8;
+    fn table_index(self) -> usize { self as usize }
+}
+
+impl Symbol for u16 {
+    const TABLE_SIZE: usize = 1 << 16;
+    fn table_index(self) -> usize { self as usize }
+}
+
+impl Symbol for u32 {}
+impl Symbol for u64 {}
+impl Symbol for usize {}
+impl Symbol for i32 {}
+impl Symbol for i64 {}
+impl Symbol for char {}
+
+/// Renumbers the symbols of `ss` densely starting at 1, leaving 0 to
+/// indicate end-of-string. Returns the new string and its alphabet size.
+pub fn reduce_alphabet<T: Symbol>(ss: &[T]) -> (Vec<DefInt>, DefInt) {
+    let n = ss.len();
+
+    if T::TABLE_SIZE == 0 {
+        let mut alphabet = ss.to_vec();
+        alphabet.par_sort_unstable();
+        alphabet.dedup();
+        let s = ss
+            .par_iter()
+            .map(|c| alphabet.binary_search(c).unwrap() as DefInt + 1)
+            .collect();
+        return (s, alphabet.len() as DefInt);
+    }
+
+    let mut flags = vec![DefInt::default(); T::TABLE_SIZE];
+    #[cfg(feature = "AW_safe")]
+    {
+        let atom_flags = (0..T::TABLE_SIZE)
+            .map(|_| DefAtomInt::new(0))
+            .collect::<Vec<_>>();
+        let f = |i: usize| {
+            let c = ss[i].table_index();
+            if atom_flags[c].load(ORDER)==0 {
+                atom_flags[c].store(1, ORDER);
+            }
+        };
+        (0..n).into_par_iter().with_gran(1024).for_each(f);
+        flags.iter_mut().enumerate().for_each(
+            |(i, f)| *f = atom_flags[i].load(ORDER)
+        );
+    }
+    #[cfg(not(feature = "AW_safe"))]
+    {
+        let flags_ptr = flags.as_ptr() as usize;
+        let f = |i: usize| {
+            let c = ss[i].table_index();
+            if flags[c]==0 {
+                unsafe { (flags_ptr as *mut DefInt).add(c).write(1); }
+            }
+        };
+        (0..n).into_par_iter().with_gran(1024).for_each(f);
+    }
+
+    let m = scan_inplace(&mut flags, false, |a, b| a + b);
+    let s = ss
+        .par_iter()
+        .map(|c| flags[c.table_index()] + 1)
+        .collect();
+    (s, m)
+}
+
+/// Packs the first symbols of every suffix into the top 96 bits of a 128-bit
+/// word and its location into the bottom 32. Returns the words and the
+/// number of symbols in each.
+fn pack_prefixes<T: Symbol>(ss: &[T]) -> (Vec<u128>, DefInt) {
+    let n = ss.len();
+    let (s, m) = reduce_alphabet(ss);
+
+    // as many symbols as fit in 96 bits, counting end-of-string
+    let radix = m as u128 + 1;
+    let (mut nchars, mut p) = (1, radix);
+    while let Some(q) = p.checked_mul(radix).filter(|&q| q <= 1 << 96) {
+        p = q;
+        nchars += 1;
+    }
+
+    let cl = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let r = (i..i+nchars).fold(0, |r, j| {
+                r * radix + s.get(j).map_or(0, |&c| c as u128)
+            });
+            (r << 32) + i as u128
+        }).collect();
+    (cl, nchars as DefInt)
+}
+
+
 #[allow(dead_code)]
 fn split_segment(
     seg_out: &mut [Seg],
@@ -134,62 +242,12 @@ fn split_segment_top(
 }
 
 #[allow(dead_code)]
-pub fn suffix_array(ss: &[DefChar], ranks: &mut [DefInt]) {
+pub fn suffix_array<T: Symbol>(ss: &[T], ranks: &mut [DefInt]) {
     let mut t = Timer::new("sa"); //t.start();
     let n = ss.len();
 
-    // renumber characters densely
-    // start numbering at 1 leaving 0 to indicate end-of-string
-    let pad = 48;
-    let mut flags = vec![DefInt::default(); 256];
-    #[cfg(feature = "AW_safe")]
-    {
-        let atom_flags = (0..256)
-            .map(|_| DefAtomInt::new(0))
-            .collect::<Vec<_>>();
-        let f = |i| {
-            if atom_flags[ss[i] as usize].load(ORDER)==0 {
-                atom_flags[ss[i] as usize].store(1, ORDER);
-            }
-        };
-        (0..n).into_par_iter().with_gran(1024).for_each(f);
-        flags.iter_mut().enumerate().for_each(
-            |(i, f)| *f = atom_flags[i].load(ORDER)
-        );
-    }
-    #[cfg(not(feature = "AW_safe"))]
-    {
-        let flags_ptr = flags.as_ptr() as usize;
-        let f = |i| {
-            if flags[ss[i] as usize]==0 {
-                unsafe { (flags_ptr as *mut DefInt).add(ss[i] as usize).write(1); }
-            }
-        };
-        (0..n).into_par_iter().with_gran(1024).for_each(f);
-    }
-
-    let m = scan_inplace(&mut flags, false, |a, b| a + b);
-
-
-    // pad the end of string with 0s
-    let s: Vec<_> = (0..n+pad)
-        .into_par_iter()
-        .map(|i| if i < n { flags[ss[i] as usize] as u8 } else { 0 })
-        .collect();
-
-    // pack characters into 128-bit word, along with the location i
-    // 96 bits for characters, and 32 for location
-    let logm = (m as f64).log2();
-    let nchars = (96.0/logm).floor() as DefInt;
-    let mut cl: Vec<_> = (0..n)
-            .into_par_iter()
-            .map(|i| {
-            let mut r = s[i] as u128;
-            for j in 1..nchars {
-                r = r * (m as u128) + (s[i+j as usize] as u128);
-            }
-            (r << 32) + i as u128
-        }).collect();
+    // renumber the symbols densely and pack the first few of every suffix
+    let (mut cl, nchars) = pack_prefixes(ss);
     t.next("copy into 128bit int");
 
     // sort based on packed words
@@ -425,62 +483,12 @@ fn atomic_split_segment_top(
 }
 
 #[allow(dead_code)]
-pub fn atomic_suffix_array(ss: &[DefChar], ranks: &[DefAtomInt]) {
+pub fn atomic_suffix_array<T: Symbol>(ss: &[T], ranks: &[DefAtomInt]) {
     let mut t = Timer::new("sa"); //t.start();
     let n = ss.len();
 
-    // renumber characters densely
-    // start numbering at 1 leaving 0 to indicate end-of-string
-    let pad = 48;
-    let mut flags = vec![DefInt::default(); 256];
-    #[cfg(feature = "AW_safe")]
-    {
-        let atom_flags = (0..256)
-            .map(|_| DefAtomInt::new(0))
-            .collect::<Vec<_>>();
-        let f = |i| {
-            if atom_flags[ss[i] as usize].load(ORDER)==0 {
-                atom_flags[ss[i] as usize].store(1, ORDER);
-            }
-        };
-        (0..n).into_par_iter().with_gran(1024).for_each(f);
-        flags.iter_mut().enumerate().for_each(
-            |(i, f)| *f = atom_flags[i].load(ORDER)
-        );
-    }
-    #[cfg(not(feature = "AW_safe"))]
-    {
-        let flags_ptr = flags.as_ptr() as usize;
-        let f = |i| {
-            if flags[ss[i] as usize]==0 {
-                unsafe { (flags_ptr as *mut DefInt).add(ss[i] as usize).write(1); }
-            }
-        };
-        (0..n).into_par_iter().with_gran(1024).for_each(f);
-    }
-
-    let m = scan_inplace(&mut flags, false, |a, b| a + b);
-
-
-    // pad the end of string with 0s
-    let s: Vec<_> = (0..n+pad)
-        .into_par_iter()
-        .map(|i| if i < n { flags[ss[i] as usize] as u8 } else { 0 })
-        .collect();
-
-    // pack characters into 128-bit word, along with the location i
-    // 96 bits for characters, and 32 for location
-    let logm = (m as f64).log2();
-    let nchars = (96.0/logm).floor() as DefInt;
-    let mut cl: Vec<_> = (0..n)
-            .into_par_iter()
-            .map(|i| {
-            let mut r = s[i] as u128;
-            for j in 1..nchars {
-                r = r * (m as u128) + (s[i+j as usize] as u128);
-            }
-            (r << 32) + i as u128
-        }).collect();
+    // renumber the symbols densely and pack the first few of every suffix
+    let (mut cl, nchars) = pack_prefixes(ss);
     t.next("copy into 128bit int");
 
     // sort based on packed words
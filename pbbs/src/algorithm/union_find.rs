@@ -26,13 +26,18 @@
 
 #[path="../common/atomics.rs"] mod atomics;
 
+use std::fmt;
 use std::mem::swap;
 use std::sync::atomic::Ordering;
 
+use clap::ValueEnum;
+use rayon::prelude::*;
+
 use atomics::atomic_cas;
 use crate::{DefIntS, DefAtomIntS};
 
 static ORD: Ordering = Ordering::Relaxed;
+static LINK_ORD: Ordering = Ordering::SeqCst;
 
 
 pub struct UnionFind {
@@ -81,34 +86,150 @@ impl UnionFind {
     }
 }
 
+/// How `AtomicUnionFind::find` shortens the paths it follows.
+#[derive(Copy, Clone, PartialEq, Eq, Default, ValueEnum, Debug)]
+pub enum FindStrategy {
+    /// point every vertex on the path to the root, in a second pass
+    Compression,
+    /// point every vertex on the path to its grandparent
+    #[default]
+    Splitting,
+    /// point every other vertex on the path to its grandparent
+    Halving,
+}
+
+impl fmt::Display for FindStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FindStrategy::Compression => write!(f, "compression"),
+            FindStrategy::Splitting => write!(f, "splitting"),
+            FindStrategy::Halving => write!(f, "halving"),
+        }
+    }
+}
+
+/// A union-find whose `find` and `unite` are lock-free and can be called
+/// concurrently. A negative parent marks a root and its magnitude is the
+/// root's rank (or size, after `union_roots`).
 pub struct AtomicUnionFind {
-    parents: Vec<DefAtomIntS>
+    parents: Vec<DefAtomIntS>,
+    strategy: FindStrategy
 }
 
 impl AtomicUnionFind {
     pub fn new(n: usize) -> Self {
-        Self { parents: (0..n).map(|_| DefAtomIntS::new(-1)).collect() }
+        Self::with_strategy(n, FindStrategy::default())
+    }
+
+    pub fn with_strategy(n: usize, strategy: FindStrategy) -> Self {
+        Self {
+            parents: (0..n)
+                .into_par_iter()
+                .map(|_| DefAtomIntS::new(-1))
+                .collect(),
+            strategy
+        }
     }
 
     fn is_root(&self, u: DefIntS) -> bool {
         self.parents[u as usize].load(ORD) < 0
     }
 
+    fn parent(&self, u: DefIntS) -> DefIntS {
+        self.parents[u as usize].load(ORD)
+    }
+
+    /// Points `u` from `p` to `a`, an ancestor of `p`. It fails only if
+    /// another thread already moved `u` further up, so the result is ignored.
+    fn shortcut(&self, u: DefIntS, p: DefIntS, a: DefIntS) {
+        let _ = self.parents[u as usize].compare_exchange(p, a, ORD, ORD);
+    }
+
     pub fn find(&self, u: DefIntS) -> DefIntS {
-        if self.is_root(u) { return u; }
-        let mut p = self.parents[u as usize].load(ORD);
-        if self.is_root(p) { return p; }
+        match self.strategy {
+            FindStrategy::Compression => self.find_compress(u),
+            FindStrategy::Splitting => self.find_split(u),
+            FindStrategy::Halving => self.find_halve(u),
+        }
+    }
 
+    fn find_compress(&self, u: DefIntS) -> DefIntS {
+        let mut r = u;
+        while !self.is_root(r) { r = self.parent(r); }
+
+        // `r` may get linked meanwhile and other finds may then skip it
         let mut u = u;
+        while u != r {
+            let p = self.parent(u);
+            if p < 0 { break; }
+            self.shortcut(u, p, r);
+            u = p;
+        }
+        r
+    }
+
+    fn find_split(&self, mut u: DefIntS) -> DefIntS {
         loop {
-            let gp = self.parents[p as usize].load(ORD);
-            self.parents[u as usize].store(gp, ORD);
+            let p = self.parent(u);
+            if p < 0 { return u; }
+            let gp = self.parent(p);
+            if gp < 0 { return p; }
+            self.shortcut(u, p, gp);
             u = p;
-            p = gp;
-            if self.is_root(p) { return p;}
         }
     }
 
+    fn find_halve(&self, mut u: DefIntS) -> DefIntS {
+        loop {
+            let p = self.parent(u);
+            if p < 0 { return u; }
+            let gp = self.parent(p);
+            if gp < 0 { return p; }
+            self.shortcut(u, p, gp);
+            u = gp;
+        }
+    }
+
+    /// Joins the sets of `u` and `v` and returns whether they were different.
+    /// The root with the lower (rank, index) is linked under the other one
+    /// with a CAS on its whole parent word, so a concurrent link or rank
+    /// change makes it retry. Since the key of a root only grows, and is
+    /// frozen once it is linked, keys strictly grow towards the roots and the
+    /// links cannot form cycles.
+    pub fn unite(&self, u: DefIntS, v: DefIntS) -> bool {
+        let (mut u, mut v) = (u, v);
+        loop {
+            (u, v) = (self.find(u), self.find(v));
+            if u == v { return false; }
+
+            let (pu, pv) = (self.parent(u), self.parent(v));
+            if pu >= 0 || pv >= 0 { continue; }
+            let (c, pc, r, pr) =
+                if (-pu, u) < (-pv, v) { (u, pu, v, pv) }
+                else { (v, pv, u, pu) };
+
+            if self.parents[c as usize]
+                .compare_exchange(pc, r, LINK_ORD, ORD)
+                .is_ok()
+            {
+                if pc == pr {
+                    let _ = self.parents[r as usize]
+                        .compare_exchange(pr, pr - 1, LINK_ORD, ORD);
+                }
+                return true;
+            }
+        }
+    }
+
+    /// The root of every vertex, which labels its connected component. Not
+    /// meant to run concurrently with `unite`.
+    pub fn components(&self) -> Vec<DefIntS> {
+        (0..self.parents.len() as DefIntS)
+            .into_par_iter()
+            .map(|u| self.find(u))
+            .collect()
+    }
+
     pub fn union_roots(&self, u: DefIntS, v: DefIntS) {
         let (mut u, mut v) = (u as usize, v as usize);
         if self.parents[u].load(ORD) < self.parents[v].load(ORD) {
@@ -33,4 +33,15 @@ pub(crate) fn comp_sort<T, F>(
     F: Fn(T, T) -> bool + Copy + Send + Sync,
 {
     parlay::internal::bucket_sort(inp, cmp, stable);
-}
\ No newline at end of file
+}
+
+pub(crate) fn nc_comp_sort<T, F>(
+    inp: &mut [T],
+    less: F,
+    stable: bool
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    parlay::internal::nc_bucket_sort(inp, less, stable);
+}
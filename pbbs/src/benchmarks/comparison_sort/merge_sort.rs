@@ -27,14 +27,22 @@
 pub(crate) fn comp_sort<T, F>(
     inp: &mut [T],
     cmp: F,
-    stable: bool
+    _stable: bool
 ) where
     T: Copy + Send + Sync,
     F: Fn(T, T) -> bool + Clone + Send,
 {
-    if stable {
-        eprintln!("merge-sort doesn't accept stable flag. Igonring.");
-    } else {
-        parlay::internal::merge_sort_inplace(inp, cmp);
-    }
+    // merge sort is always stable.
+    parlay::internal::merge_sort_inplace(inp, cmp);
+}
+
+pub(crate) fn nc_comp_sort<T, F>(
+    inp: &mut [T],
+    less: F,
+    _stable: bool
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    parlay::internal::nc_merge_sort_inplace(inp, less);
 }
@@ -33,8 +33,23 @@ pub(crate) fn comp_sort<T, F>(
     F: Fn(T, T) -> bool + Copy + Send + Sync,
 {
     if stable {
-        eprintln!("quick-sort doesn't accept stable flag. Igonring.");
+        eprintln!("quick-sort doesn't accept stable flag. Ignoring.");
     } else {
         parlay::internal::quick_sort(inp, cmp);
     }
 }
+
+pub(crate) fn nc_comp_sort<T, F>(
+    inp: &mut [T],
+    less: F,
+    stable: bool
+) where
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    if stable {
+        eprintln!("quick-sort doesn't accept stable flag. Ignoring.");
+    } else {
+        parlay::internal::nc_quick_sort(inp, less);
+    }
+}
@@ -45,3 +45,23 @@ pub fn comp_sort<T, F>(
         inp.par_sort_unstable_by(cmp);
     }
 }
+
+pub fn nc_comp_sort<T, F>(
+    inp: &mut [T],
+    less: F,
+    stable: bool
+) where
+    T: Send,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    let cmp = |a: &T, b: &T| {
+        if less(a, b) { std::cmp::Ordering::Less }
+        else if less(b, a) { std::cmp::Ordering::Greater }
+        else { std::cmp::Ordering::Equal }
+    };
+    if stable {
+        inp.par_sort_by(cmp);
+    } else {
+        inp.par_sort_unstable_by(cmp);
+    }
+}
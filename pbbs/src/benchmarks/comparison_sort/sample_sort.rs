@@ -37,3 +37,14 @@ pub fn comp_sort<T, F>(
     };
     parlay::internal::sample_sort(inp_alias, inp, cmp, stable);
 }
+
+pub fn nc_comp_sort<T, F>(
+    inp: &mut [T],
+    less: F,
+    stable: bool
+) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Sync,
+{
+    parlay::internal::nc_sample_sort_inplace(inp, less, stable);
+}
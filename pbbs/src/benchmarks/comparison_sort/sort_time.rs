@@ -29,14 +29,22 @@
 #[path ="../macros.rs"] mod macros;
 #[path ="../../common/io.rs"] mod io;
 #[path ="mod.rs"] mod comparison_sort;
+#[path ="../../common/external_sort.rs"] mod external_sort;
 
+use std::fs;
+use std::fmt::Debug;
+use std::io::{BufRead, BufReader};
 use std::time::Duration;
-use io::{read_file_to_vec, write_slice_to_file_seq};
+use rayon::prelude::*;
+use io::{lines, write_slice_to_file_seq};
+use external_sort::{external_sort, read_head, tmp_dir};
 
 
 define_args!(
     Algs::MERGE,
-    (stable, bool, false)
+    (stable, bool, false),
+    (memory_limit, usize, 0),
+    (tmp_dir, String, String::new())
 );
 
 define_algs!(
@@ -49,6 +57,34 @@ define_algs!(
 );
 
 
+fn get_sort<T, F>(alg: Algs) -> fn(&mut [T], F, bool) where
+    T: Copy + Send + Sync + Default,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+{
+    match alg {
+        Algs::MERGE     => comparison_sort::merge_sort::comp_sort,
+        Algs::QUICK     => comparison_sort::quick_sort::comp_sort,
+        Algs::BUCKET    => comparison_sort::bucket_sort::comp_sort,
+        Algs::SAMPLE    => comparison_sort::sample_sort::comp_sort,
+        Algs::STD       => comparison_sort::std::comp_sort,
+        Algs::RAYON     => comparison_sort::rayon::comp_sort,
+    }
+}
+
+fn get_nc_sort<T, F>(alg: Algs) -> fn(&mut [T], F, bool) where
+    T: Send + Sync,
+    F: Fn(&T, &T) -> bool + Copy + Send + Sync,
+{
+    match alg {
+        Algs::MERGE     => comparison_sort::merge_sort::nc_comp_sort,
+        Algs::QUICK     => comparison_sort::quick_sort::nc_comp_sort,
+        Algs::BUCKET    => comparison_sort::bucket_sort::nc_comp_sort,
+        Algs::SAMPLE    => comparison_sort::sample_sort::nc_comp_sort,
+        Algs::STD       => comparison_sort::std::nc_comp_sort,
+        Algs::RAYON     => comparison_sort::rayon::nc_comp_sort,
+    }
+}
+
 pub fn run<T, F>(
     alg: Algs,
     rounds: usize, 
@@ -59,14 +95,7 @@ pub fn run<T, F>(
     T: Copy + Send + Sync + Default,
     F: Fn(T, T) -> bool + Copy + Send + Sync,
 {
-    let f = match alg {
-        Algs::MERGE     => comparison_sort::merge_sort::comp_sort,
-        Algs::QUICK     => comparison_sort::quick_sort::comp_sort,
-        Algs::BUCKET    => comparison_sort::bucket_sort::comp_sort,
-        Algs::SAMPLE    => comparison_sort::sample_sort::comp_sort,
-        Algs::STD       => comparison_sort::std::comp_sort,
-        Algs::RAYON     => comparison_sort::rayon::comp_sort,
-    };
+    let f = get_sort(alg);
 
     let mut r = parlay::maybe_uninit_vec![T::default(); inp.len()];
     let r_clone = unsafe { (&mut r[..] as *mut [T]).as_mut().unwrap() };
@@ -83,30 +112,224 @@ pub fn run<T, F>(
     (r, mean)
 }
 
-fn main() {
-    init!();
+/// like `run`, but sorts non-`Copy` elements by moving them
+pub fn run_nc<T, F>(
+    alg: Algs,
+    rounds: usize,
+    stable: bool,
+    less: F,
+    inp: &[T]
+) -> (Vec<T>, Duration) where
+    T: Clone + Send + Sync,
+    F: Fn(&T, &T) -> bool + Copy + Send + Sync,
+{
+    let f = get_nc_sort(alg);
 
-    let args = Args::parse();
+    let mut r: Vec<T> = vec![];
+    let r_ptr = &r as *const Vec<T> as usize;
 
-    let arr: Vec<i32> = read_file_to_vec(
-        &args.ifname,
-        Some { 0: |w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")} }
+    let mean = time_loop(
+        "sort",
+        rounds,
+        Duration::new(1, 0),
+        || { unsafe {
+            *(r_ptr as *mut Vec<T>).as_mut().unwrap() = inp.par_iter().cloned().collect();
+        }},
+        || { f(&mut r, less, stable) },
+        || {}
     );
 
-    let less = |a: i32, b: i32| a < b;
+    (r, mean)
+}
 
+fn sort_and_write<T, F, W>(args: Args, inp: &[T], less: F, to_string: W)
+where
+    T: Copy + Send + Sync + Default + Debug,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    W: Fn(&T) -> String + Send + Sync,
+{
     let (r, d) = run(
         args.algorithm,
         args.rounds,
         args.stable,
         less,
-        &arr
+        inp
     );
 
     finalize!(
         args,
         r,
         d,
-        write_slice_to_file_seq(&r, args.ofname)
+        {
+            let r: Vec<_> = r.par_iter().map(&to_string).collect();
+            write_slice_to_file_seq(&r, args.ofname)
+        }
     );
 }
+
+/// sorts the input out of core with `args.memory_limit` bytes of memory
+fn sort_external<T, P, F, W>(args: Args, parse: P, less: F, to_string: W)
+where
+    T: Copy + Send + Sync + Default + Debug,
+    P: Fn(&str) -> Option<T> + Send + Sync,
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    W: Fn(&T) -> String + Send + Sync,
+{
+    let f = get_sort(args.algorithm);
+
+    let tmp_dir = tmp_dir(&args.tmp_dir);
+    let tmp_out = tmp_dir.join(format!("pbbs_sort_{}", std::process::id()));
+    let ofname = if args.ofname.is_empty() {
+        tmp_out.to_str().unwrap().to_string()
+    } else { args.ofname.clone() };
+
+    let d = time_loop(
+        "sort",
+        args.rounds,
+        Duration::new(0, 0),
+        || {},
+        || {
+            external_sort(
+                &args.ifname,
+                &ofname,
+                args.memory_limit,
+                &tmp_dir,
+                &parse,
+                |c| f(c, less, args.stable),
+                less,
+                &to_string
+            ).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+        },
+        || {}
+    );
+
+    // the result is already on disk; only its start is shown
+    let r: Vec<T> = if args.ofname.is_empty() {
+        let r = read_head(&ofname, 20, &parse);
+        let _ = fs::remove_file(tmp_out);
+        r
+    } else { vec![] };
+    finalize!(args, r, d, ());
+}
+
+/// parses every line of `w` with `parse`, failing on any malformed one
+fn parse_lines<T, P>(w: &[&str], parse: P) -> Result<Vec<T>, String>
+where
+    T: Send,
+    P: Fn(&str) -> Option<T> + Send + Sync,
+{
+    w
+        .par_iter()
+        .map(|l| parse(l).ok_or_else(|| format!("cannot parse line {l:?}")))
+        .collect()
+}
+
+fn parse_pair<T: std::str::FromStr>(l: &str) -> Option<(T, T)> {
+    let mut w = l.split_whitespace().map(|x| x.parse().ok());
+    Some((w.next()??, w.next()??))
+}
+
+fn main_external(args: Args) {
+    let mut header = String::new();
+    BufReader::new(fs::File::open(&args.ifname)
+        .expect("cannot open input file"))
+        .read_line(&mut header)
+        .expect("cannot read input file");
+
+    match header.trim() {
+        "sequenceDouble" => sort_external(
+            args,
+            |l| l.trim().parse::<f64>().ok(),
+            |a, b| a < b,
+            f64::to_string
+        ),
+        "sequenceDoublePair" => sort_external(
+            args,
+            parse_pair::<f64>,
+            |a, b| a.0 < b.0,
+            |(a, b)| format!("{a} {b}")
+        ),
+        "sequenceIntPair" => sort_external(
+            args,
+            parse_pair::<i32>,
+            |a, b| a.0 < b.0,
+            |(a, b)| format!("{a} {b}")
+        ),
+        "sequenceInt" => sort_external(
+            args,
+            |l| l.trim().parse::<i32>().ok(),
+            |a, b| a < b,
+            i32::to_string
+        ),
+        h => {
+            eprintln!("--memory-limit does not support {h} inputs");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    init!();
+
+    let args = Args::parse();
+    if args.memory_limit > 0 { return main_external(args); }
+
+    let s = fs::read_to_string(&args.ifname)
+        .expect("cannot read input file");
+    let (header, body) = s.split_once('\n').unwrap_or((&s, ""));
+    let header = header.trim();
+
+    // the strings are owned and sorted by moving them; empty lines are
+    // empty strings
+    if header.starts_with("sequenceChar") || header == "sequenceString" {
+        let arr: Vec<String> = body
+            .par_split_terminator('\n')
+            .map(String::from)
+            .collect();
+        let (r, d) = run_nc(
+            args.algorithm,
+            args.rounds,
+            args.stable,
+            |a: &String, b: &String| a < b,
+            &arr
+        );
+        finalize!(
+            args,
+            r,
+            d,
+            write_slice_to_file_seq(&r, args.ofname)
+        );
+        return;
+    }
+
+    // pairs are compared by their first element only, as in PBBS
+    let w = lines(body);
+    let res = match header {
+        "sequenceInt" => parse_lines(&w, |l| l.trim().parse::<i32>().ok())
+            .map(|arr| sort_and_write(args, &arr, |a, b| a < b, i32::to_string)),
+        "sequenceDouble" => parse_lines(&w, |l| l.trim().parse::<f64>().ok())
+            .map(|arr| sort_and_write(args, &arr, |a, b| a < b, f64::to_string)),
+        "sequenceDoublePair" => parse_lines(&w, parse_pair::<f64>)
+            .map(|arr| sort_and_write(
+                args,
+                &arr,
+                |a, b| a.0 < b.0,
+                |(a, b)| format!("{a} {b}")
+            )),
+        "sequenceIntPair" => parse_lines(&w, parse_pair::<i32>)
+            .map(|arr| sort_and_write(
+                args,
+                &arr,
+                |a, b| a.0 < b.0,
+                |(a, b)| format!("{a} {b}")
+            )),
+        h => Err(format!("unsupported input type {h:?}")),
+    };
+    if let Err(e) = res {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
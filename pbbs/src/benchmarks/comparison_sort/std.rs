@@ -44,3 +44,23 @@ pub fn comp_sort<T, F>(
         inp.sort_unstable_by(cmp);
     }
 }
+
+pub fn nc_comp_sort<T, F>(
+    inp: &mut [T],
+    less: F,
+    stable: bool
+) where
+    F: Fn(&T, &T) -> bool,
+{
+    let cmp = |a: &T, b: &T| {
+        if less(a, b) { std::cmp::Ordering::Less }
+        else if less(b, a) { std::cmp::Ordering::Greater }
+        else { std::cmp::Ordering::Equal }
+    };
+
+    if stable {
+        inp.sort_by(cmp);
+    } else {
+        inp.sort_unstable_by(cmp);
+    }
+}
@@ -29,7 +29,10 @@ use clap::Parser;
 use rayon::prelude::*;
 
 #[path ="../../common/io.rs"] mod io;
-use io::read_file_to_vec;
+use io::{
+    lines, pairs_from_lines, read_file_to_vec, read_with_header,
+    values_from_lines
+};
 
 #[derive(Parser, Debug)]
 #[clap(version, about, long_about = None)]
@@ -45,6 +48,14 @@ struct Args {
     /// the number of buckets
     #[clap(short, long, value_parser, required=false, default_value_t=0)]
     buckets: usize,
+
+    /// the input is "key weight" pairs whose weights are summed
+    #[clap(long, value_parser, required=false, default_value_t=false)]
+    weights: bool,
+
+    /// the bucket boundaries of a sequenceDouble input
+    #[clap(long, value_parser, required=false, default_value_t=String::new())]
+    bounds: String,
 }
 
 pub fn check(inp: &mut [usize], out: &mut [usize], buckets: usize) -> bool {
@@ -68,9 +79,63 @@ pub fn check(inp: &mut [usize], out: &mut [usize], buckets: usize) -> bool {
     } else { true }
 }
 
+pub fn check_weighted(inp: &[(usize, u64)], out: &[u64], buckets: usize) -> bool {
+    assert_eq!(out.len(), buckets);
+
+    let mut hist = vec![0; buckets];
+    for &(k, w) in inp.iter() { hist[k] += w; }
+
+    let diff_count = hist.iter().zip(out).filter(|(a, b)| a != b).count();
+    if diff_count!=0 {
+        eprintln!("output file has {diff_count} differences.");
+        false
+    } else { true }
+}
+
+pub fn check_bounds(inp: &[f64], bounds: &[f64], out: &[usize]) -> bool {
+    assert_eq!(out.len(), bounds.len().saturating_sub(1));
+
+    // bucket i holds the values in [bounds[i], bounds[i+1])
+    let mut hist = vec![0; out.len()];
+    for &x in inp.iter() {
+        for i in 0..hist.len() {
+            if bounds[i] <= x && x < bounds[i+1] { hist[i] += 1; break; }
+        }
+    }
+
+    let diff_count = hist.iter().zip(out).filter(|(a, b)| a != b).count();
+    if diff_count!=0 {
+        eprintln!("output file has {diff_count} differences.");
+        false
+    } else { true }
+}
+
+fn read_doubles(fname: &str) -> Vec<f64> {
+    read_with_header(fname, "sequenceDouble")
+        .and_then(|s| values_from_lines(&lines(&s)[1..]))
+        .unwrap_or_else(|e| { eprintln!("{e}"); std::process::exit(1); })
+}
+
 fn main() {
     eprintln!("WARNING: hist_check needs improvement.");
     let args = Args::parse();
+    if !args.bounds.is_empty() {
+        let inp = read_doubles(&args.ifname);
+        let bounds = read_doubles(&args.bounds);
+        let out = read_file_to_vec(&args.rfname, Some(|_: &[&str]| {}));
+        if check_bounds(&inp, &bounds, &out) { println!("OK"); }
+        else { eprintln!("ERR"); std::process::exit(1); }
+        return;
+    }
+    if args.weights {
+        let inp = read_with_header(&args.ifname, "sequenceIntPair")
+            .and_then(|s| pairs_from_lines(&lines(&s)[1..]))
+            .unwrap_or_else(|e| { eprintln!("{e}"); std::process::exit(1); });
+        let out = read_file_to_vec(&args.rfname, Some(|_: &[&str]| {}));
+        if check_weighted(&inp, &out, args.buckets) { println!("OK"); }
+        else { eprintln!("ERR"); std::process::exit(1); }
+        return;
+    }
     let mut inp = read_file_to_vec(
         &args.ifname,
         Some(|a: &[&str]| assert_eq!(a[0], "sequenceInt"))
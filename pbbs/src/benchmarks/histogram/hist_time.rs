@@ -25,6 +25,7 @@
 // ============================================================================
 
 use std::time::Duration;
+use rayon::prelude::*;
 
 #[path ="mod.rs"] mod hist;
 #[path ="../../misc.rs"] mod misc;
@@ -33,11 +34,16 @@ use std::time::Duration;
 
 use misc::*;
 use hist::{sequential, parallel};
-use io::{read_big_file_to_vec, write_slice_to_file_seq};
+use io::{
+    lines, pairs_from_lines, read_big_file_to_vec, read_with_header,
+    values_from_lines, write_slice_to_file_seq
+};
 
 define_args!(
     Algs::PARALLEL,
-    (buckets, usize, 0)
+    (buckets, usize, 0),
+    (weights, bool, false),
+    (bounds, String, String::new())
 );
 
 define_algs!(
@@ -45,36 +51,116 @@ define_algs!(
     (SEQUENTIAL, "sequential")
 );
 
-pub fn run(alg: Algs, rounds: usize, buckets: usize, arr: &[u32]) -> (Vec<u32>, Duration) {
-    let f = match alg {
-        Algs::PARALLEL => {parallel::hist},
-        Algs::SEQUENTIAL => {sequential::hist}
-    };
-
+pub fn run<T: Sync, R, F>(
+    f: F,
+    rounds: usize,
+    buckets: usize,
+    arr: &[T]
+) -> (Vec<R>, Duration) where
+    F: Fn(&[T], usize, &mut Vec<R>),
+{
     let mut r = vec![];
-    let r_ptr = &r as *const Vec<u32> as usize;
+    let r_ptr = &r as *const Vec<R> as usize;
 
     let mean = time_loop(
         "hist",
         rounds,
         Duration::new(1, 0),
-        || { unsafe { *(r_ptr as *mut Vec<u32>) = vec![]; } },
-        || { f(&arr, buckets, &mut r); },
+        || { unsafe { *(r_ptr as *mut Vec<R>).as_mut().unwrap() = vec![]; } },
+        || { f(arr, buckets, &mut r); },
         || {}
     );
     (r, mean)
 }
 
+/// reads a `sequenceIntPair` file of "key weight" lines, whose keys must be
+/// below `buckets`
+fn read_weighted_from_file(
+    fname: &str,
+    buckets: usize
+) -> Result<Vec<(u32, u64)>, String> {
+    if buckets == 0 { return Err("--weights needs --buckets".to_string()); }
+    let s = read_with_header(fname, "sequenceIntPair")?;
+    let arr: Vec<(u32, u64)> = pairs_from_lines(&lines(&s)[1..])?;
+    match arr.par_iter().find_any(|&&(k, _)| k as usize >= buckets) {
+        Some((k, _)) => Err(format!("key {k} is not below --buckets {buckets}")),
+        None => Ok(arr),
+    }
+}
+
+/// reads a `sequenceDouble` file
+fn read_doubles_from_file(fname: &str) -> Result<Vec<f64>, String> {
+    let s = read_with_header(fname, "sequenceDouble")?;
+    values_from_lines(&lines(&s)[1..])
+}
+
+fn exit_on_err<T>(r: Result<T, String>) -> T {
+    r.unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    })
+}
+
 fn main() {
     init!();
     let args = Args::parse();
+
+    // the input values are counted in the buckets between the boundaries
+    if !args.bounds.is_empty() {
+        let arr = exit_on_err(read_doubles_from_file(&args.ifname));
+        let bounds = exit_on_err(read_doubles_from_file(&args.bounds));
+        if !bounds.windows(2).all(|b| b[0] <= b[1]) {
+            eprintln!("{}: the boundaries are not sorted", args.bounds);
+            std::process::exit(1);
+        }
+        let f: fn(&[f64], &[f64], &mut Vec<DefInt>) = match args.algorithm {
+            Algs::PARALLEL => parallel::bounds_hist,
+            Algs::SEQUENTIAL => sequential::bounds_hist,
+        };
+        let (r, d) = run(
+            |a, _, r| f(a, &bounds, r),
+            args.rounds,
+            args.buckets,
+            &arr
+        );
+
+        finalize!(
+            args,
+            r,
+            d,
+            write_slice_to_file_seq(&r, args.ofname)
+        );
+        return;
+    }
+
+    if args.weights {
+        let arr = exit_on_err(read_weighted_from_file(&args.ifname, args.buckets));
+        let f = match args.algorithm {
+            Algs::PARALLEL => parallel::weighted_hist,
+            Algs::SEQUENTIAL => sequential::weighted_hist,
+        };
+        let (r, d) = run(f, args.rounds, args.buckets, &arr);
+
+        finalize!(
+            args,
+            r,
+            d,
+            write_slice_to_file_seq(&r, args.ofname)
+        );
+        return;
+    }
+
     let mut arr = Vec::new();
     read_big_file_to_vec(
         &args.ifname,
-        Some { 0: |w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")} },
+        Some(|w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")}),
         &mut arr
     );
-    let (r, d) = run(args.algorithm, args.rounds, args.buckets, &arr);
+    let f = match args.algorithm {
+        Algs::PARALLEL => parallel::hist,
+        Algs::SEQUENTIAL => sequential::hist,
+    };
+    let (r, d) = run::<u32, u32, _>(f, args.rounds, args.buckets, &arr);
 
     finalize!(
         args,
@@ -24,9 +24,11 @@
 // SOFTWARE.
 // ============================================================================
 
-use num_traits::PrimInt;
+use num_traits::{PrimInt, Zero};
 
-use parlay::internal::group_by::histogram_by_index;
+use parlay::internal::group_by::{
+    histogram_by_bounds, histogram_by_index, weighted_histogram_by_index
+};
 use crate::DefInt;
 
 
@@ -37,3 +39,18 @@ pub fn hist<T: PrimInt + Default + Send + Sync>(
 ) {
     histogram_by_index::<T, DefInt>(inp, buckets, res);
 }
+
+pub fn weighted_hist<K, W>(inp: &[(K, W)], buckets: usize, res: &mut Vec<W>)
+where
+    K: PrimInt + Default + Send + Sync,
+    W: Zero + Copy + Default + Send + Sync,
+{
+    weighted_histogram_by_index(inp, buckets, res);
+}
+
+pub fn bounds_hist<T>(inp: &[T], bounds: &[T], res: &mut Vec<DefInt>)
+where
+    T: PartialOrd + Copy + Send + Sync,
+{
+    histogram_by_bounds(inp, bounds, res);
+}
@@ -24,7 +24,9 @@
 // SOFTWARE.
 // ============================================================================
 
-use num_traits::PrimInt;
+use num_traits::{PrimInt, Zero};
+
+use crate::DefInt;
 
 
 pub fn hist<T>(inp: &[T], buckets: usize, res: &mut Vec<T>)
@@ -39,3 +41,28 @@ where
 
     *res = result;
 }
+
+pub fn weighted_hist<K, W>(inp: &[(K, W)], buckets: usize, res: &mut Vec<W>)
+where
+    K: PrimInt,
+    W: Zero + Copy,
+{
+    let mut result = vec![W::zero(); buckets];
+    for &(k, w) in inp.iter() {
+        let i = k.to_usize().unwrap();
+        result[i] = result[i] + w;
+    }
+
+    *res = result;
+}
+
+pub fn bounds_hist<T: PartialOrd>(inp: &[T], bounds: &[T], res: &mut Vec<DefInt>) {
+    let mut result = vec![0; bounds.len().saturating_sub(1)];
+    for e in inp.iter() {
+        // the number of boundaries at or below e, 0 for NaNs
+        let i = bounds.partition_point(|b| b <= e);
+        if i > 0 && i < bounds.len() { result[i-1] += 1; }
+    }
+
+    *res = result;
+}
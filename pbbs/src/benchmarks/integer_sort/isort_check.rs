@@ -24,9 +24,18 @@
 // SOFTWARE.
 // ============================================================================
 
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::fs;
+use std::process::exit;
+use std::str::FromStr;
 use clap::Parser;
 
-use pbbs::common::io::read_file_to_vec;
+#[path ="../../common/io.rs"] mod io;
+
+use io::lines;
 
 #[derive(Parser, Debug)]
 #[clap(version, about, long_about = None)]
@@ -34,23 +43,77 @@ struct Args {
     /// sort results filename
     #[clap(value_parser, required=true)]
     rfname: String,
+
+    /// the input filename
+    #[clap(value_parser, required=true)]
+    ifname: String,
 }
 
-pub fn check(arr: &[usize]) -> Result<(), String> {
-    let mut violation_no = 0usize;
-    for i in 0..arr.len()-1 {
-        if arr[i] > arr[i+1] { violation_no += 1; }
+fn read_rows<T: FromStr>(fname: &str) -> Vec<Vec<T>> {
+    let s = fs::read_to_string(fname)
+        .expect("cannot read file");
+    lines(&s)
+        .into_iter()
+        .filter(|l| !l.starts_with("sequence"))
+        .map(|l| l
+            .split_whitespace()
+            .map(|x| x.parse().unwrap_or_else(|_| {
+                eprintln!("{fname}: cannot parse {x:?}");
+                exit(1);
+            }))
+            .collect())
+        .collect()
+}
+
+/// The result must be the input stably sorted by the first column (the key)
+/// according to `cmp`, which for pair inputs also means that equal keys keep
+/// the input order of their values.
+pub fn check<T, F>(inp: &[Vec<T>], r: &[Vec<T>], cmp: F) -> bool
+where
+    T: Clone + Debug,
+    F: Fn(&T, &T) -> Ordering,
+{
+    if inp.len() != r.len() {
+        eprintln!("expected {} elements, got {}", inp.len(), r.len());
+        return false;
+    }
+    let mut expected = inp.to_vec();
+    expected.sort_by(|x, y| cmp(&x[0], &y[0]));
+    let same = |a: &Vec<T>, b: &Vec<T>| {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| cmp(x, y).is_eq())
+    };
+    match expected.iter().zip(r).position(|(a, b)| !same(a, b)) {
+        None => true,
+        Some(i) => {
+            if i > 0 && cmp(&r[i][0], &r[i-1][0]).is_lt() {
+                eprintln!("not sorted at position {i}");
+            } else {
+                eprintln!("wrong or unstable element at position {i}: \
+                    expected {:?}, got {:?}", expected[i], r[i]);
+            }
+            false
+        }
     }
-    if violation_no != 0 { Err("{violation_no} violations}") }
-    else { Ok(()) }
 }
 
 fn main() {
     let args = Args::parse();
-    let r: Vec<usize> = read_file_to_vec(
-        &args.rfname,
-        Some{ 0:|_: &[&str]| {debug_assert_eq!(true, true)} }
-    );
+    let s = fs::read_to_string(&args.ifname)
+        .expect("cannot read file");
+    let header = s.split('\n').next().unwrap_or("").trim();
+
+    // doubles are ordered as by their order-preserving keys, where -0.0 is
+    // below 0.0 and NaNs go to the ends
+    let ok = if header == "sequenceDouble" {
+        let inp = read_rows::<f64>(&args.ifname);
+        let r = read_rows::<f64>(&args.rfname);
+        check(&inp, &r, f64::total_cmp)
+    } else {
+        let inp = read_rows::<i128>(&args.ifname);
+        let r = read_rows::<i128>(&args.rfname);
+        check(&inp, &r, i128::cmp)
+    };
 
-    check(&r).unwrap();
+    if ok { println!("OK"); }
+    else { eprintln!("ERR"); exit(1); }
 }
@@ -29,38 +29,53 @@
 #[path ="mod.rs"] mod isort;
 #[path ="../macros.rs"] mod macros;
 #[path ="../../common/io.rs"] mod io;
+#[path ="../../common/external_sort.rs"] mod external_sort;
 
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::fmt::Debug;
+use std::str::FromStr;
 use std::time::Duration;
-use io::{read_big_file_to_vec, write_slice_to_file_seq};
+use rayon::prelude::*;
+use isort::parallel_radix_sort::{IntSortable, KeyType};
+use io::{
+    lines, pairs_from_lines, read_with_header, values_from_lines,
+    write_slice_to_file_seq
+};
+use external_sort::{
+    block_bytes, external_sort, for_each_block, read_head, tmp_dir
+};
 
 define_args!(
     Algs::PARRADIX,
-    (bits, usize, 0)
+    (bits, usize, 0),
+    (memory_limit, usize, 0),
+    (tmp_dir, String, String::new())
 );
 
 define_algs!(
     (PARRADIX, "parradix")
 );
 
-pub fn run(
+pub fn run<T: IntSortable>(
     alg: Algs,
     rounds: usize,
-    g: &[u32],
+    g: &[T],
     bits: usize
-) -> (Vec<u32>, Duration)
+) -> (Vec<T>, Duration)
 {
     let f = match alg {
         Algs::PARRADIX => isort::parallel_radix_sort::int_sort,
     };
 
     let mut r = parlay::maybe_uninit_vec![];
-    let r_ptr = &r as *const Vec<u32> as usize;
+    let r_ptr = &r as *const Vec<T> as usize;
 
     let mean = time_loop(
         "isort",
         rounds,
         Duration::new(1, 0),
-        || { unsafe { *(r_ptr as *mut Vec<u32>).as_mut().unwrap() = vec![];}},
+        || { unsafe { *(r_ptr as *mut Vec<T>).as_mut().unwrap() = vec![];}},
         || { f(&g, bits, &mut r); },
         || {}
     );
@@ -68,22 +83,230 @@ pub fn run(
     (r, mean)
 }
 
-fn main() {
-    init!();
+fn read_pairs_from_file(fname: &str) -> Result<Vec<(u32, u32)>, String> {
+    let s = read_with_header(fname, "sequenceIntPair")?;
+    pairs_from_lines(&lines(&s)[1..])
+}
 
-    let args = Args::parse();
-    let mut arr = Vec::new();
-    read_big_file_to_vec(
-        &args.ifname,
-        Some { 0: |w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")} },
-        &mut arr
-    );
-    let (r, d) = run(args.algorithm, args.rounds, &arr, args.bits);
+/// the keys of a file read to `s`, parsed straight into `T`
+fn parse_keys<T: FromStr + Send>(s: &str) -> Result<Vec<T>, String> {
+    values_from_lines(lines(s).get(1..).unwrap_or(&[]))
+}
 
+fn sort_and_write<T: IntSortable + Debug + ToString>(args: &Args, arr: &[T]) {
+    let (r, d) = run(args.algorithm, args.rounds, arr, args.bits);
     finalize!(
         args,
         r,
         d,
-        write_slice_to_file_seq(&r, args.ofname)
+        write_slice_to_file_seq(&r, &args.ofname)
     );
 }
+
+/// the smallest and the largest of `keys`
+fn key_range(keys: &[i128]) -> (i128, i128) {
+    keys
+        .par_iter()
+        .map(|&k| (k, k))
+        .reduce(|| (i128::MAX, i128::MIN), |a, b| (a.0.min(b.0), a.1.max(b.1)))
+}
+
+fn key_type(min: i128, max: i128) -> Result<KeyType, String> {
+    KeyType::of_range(min, max)
+        .ok_or_else(|| format!("keys between {min} and {max} do not fit in 64 bits"))
+}
+
+fn narrow<T: Send>(keys: Vec<i128>, f: fn(i128) -> T) -> Vec<T> {
+    keys.into_par_iter().map(f).collect()
+}
+
+/// sorts the keys of a `sequenceInt` file as the narrowest of u32, i32,
+/// u64 and i64 that holds all of them. The keys are parsed once at full
+/// width and then narrowed.
+fn sort_ints(args: &Args, s: String) -> Result<(), String> {
+    let keys = parse_keys::<i128>(&s)?;
+    drop(s);
+    let (min, max) = key_range(&keys);
+    match key_type(min, max)? {
+        KeyType::U32 => sort_and_write(args, &narrow(keys, |k| k as u32)),
+        KeyType::I32 => sort_and_write(args, &narrow(keys, |k| k as i32)),
+        KeyType::U64 => sort_and_write(args, &narrow(keys, |k| k as u64)),
+        KeyType::I64 => sort_and_write(args, &narrow(keys, |k| k as i64)),
+    }
+    Ok(())
+}
+
+fn parse_pair(l: &str) -> Option<(u32, u32)> {
+    let mut w = l.split_whitespace().map(|x| x.parse().ok());
+    Some((w.next()??, w.next()??))
+}
+
+fn sort_chunk<T: IntSortable>(args: &Args, c: &mut Vec<T>) {
+    let mut r = vec![];
+    match args.algorithm {
+        Algs::PARRADIX => isort::parallel_radix_sort::int_sort(c, args.bits, &mut r),
+    }
+    *c = r;
+}
+
+/// sorts the input out of core with `args.memory_limit` bytes of memory.
+/// Keys are compared by `IntSortable::key`, so signed keys are sorted and
+/// merged by their `OrderedKey`.
+fn run_external<T, P, W>(args: &Args, parse: P, write: W)
+where
+    T: IntSortable + Default + Debug,
+    P: Fn(&str) -> Option<T> + Send + Sync,
+    W: Fn(&T) -> String + Send + Sync,
+{
+    let tmp_dir = tmp_dir(&args.tmp_dir);
+    let tmp_out = tmp_dir.join(format!("pbbs_isort_{}", std::process::id()));
+    let ofname = if args.ofname.is_empty() {
+        tmp_out.to_str().unwrap().to_string()
+    } else { args.ofname.clone() };
+
+    let d = time_loop(
+        "isort",
+        args.rounds,
+        Duration::new(0, 0),
+        || {},
+        || {
+            external_sort(
+                &args.ifname,
+                &ofname,
+                args.memory_limit,
+                &tmp_dir,
+                &parse,
+                |c| sort_chunk(args, c),
+                |a: T, b: T| a.key() < b.key(),
+                &write
+            ).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+        },
+        || {}
+    );
+
+    // the result is already on disk; only its start is shown
+    let r: Vec<T> = if args.ofname.is_empty() {
+        let r = read_head(&ofname, 20, &parse);
+        let _ = fs::remove_file(tmp_out);
+        r
+    } else { vec![] };
+    finalize!(args, r, d, ());
+}
+
+/// the smallest and the largest key of a `sequenceInt` file, read one block
+/// at a time
+fn key_range_of_file(args: &Args) -> Result<(i128, i128), String> {
+    let mut range = (i128::MAX, i128::MIN);
+    for_each_block(&args.ifname, block_bytes(args.memory_limit), |w| {
+        let keys: Vec<i128> = w
+            .into_par_iter()
+            .map(|l| l.trim().parse().map_err(|_| format!("cannot parse line {l:?}")))
+            .collect::<Result<_, _>>()?;
+        let (min, max) = key_range(&keys);
+        range = (range.0.min(min), range.1.max(max));
+        Ok(())
+    })?;
+    Ok(range)
+}
+
+/// PBBS's `sequenceInt` keys are signed. As in memory, they are sorted as
+/// the narrowest of u32, i32, u64 and i64 that holds them all, which takes
+/// an extra pass over the file to find their range.
+fn main_external(args: &Args, header: &str) -> Result<(), String> {
+    match header {
+        "sequenceIntPair" => run_external(
+            args,
+            parse_pair,
+            |(k, v)| format!("{k} {v}")
+        ),
+        "sequenceInt" => {
+            let (min, max) = key_range_of_file(args)?;
+            match key_type(min, max)? {
+                KeyType::U32 => run_external(
+                    args,
+                    |l| l.trim().parse::<u32>().ok(),
+                    u32::to_string
+                ),
+                KeyType::I32 => run_external(
+                    args,
+                    |l| l.trim().parse::<i32>().ok(),
+                    i32::to_string
+                ),
+                KeyType::U64 => run_external(
+                    args,
+                    |l| l.trim().parse::<u64>().ok(),
+                    u64::to_string
+                ),
+                KeyType::I64 => run_external(
+                    args,
+                    |l| l.trim().parse::<i64>().ok(),
+                    i64::to_string
+                ),
+            }
+        },
+        h => return Err(format!("--memory-limit does not support {h} inputs")),
+    }
+    Ok(())
+}
+
+fn main() {
+    init!();
+
+    let args = Args::parse();
+    let header = BufReader::new(fs::File::open(&args.ifname)
+        .expect("cannot read input file"))
+        .lines()
+        .next()
+        .unwrap_or(Ok(String::new()))
+        .expect("cannot read input file");
+    let pairs = header.trim() == "sequenceIntPair";
+
+    if args.memory_limit > 0 {
+        if let Err(e) = main_external(&args, header.trim()) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if pairs {
+        let arr = read_pairs_from_file(&args.ifname).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        let (r, d) = run(args.algorithm, args.rounds, &arr, args.bits);
+        finalize!(
+            args,
+            r,
+            d,
+            {
+                let r: Vec<_> = r
+                    .par_iter()
+                    .map(|(k, v)| format!("{k} {v}"))
+                    .collect();
+                write_slice_to_file_seq(&r, args.ofname)
+            }
+        );
+        return;
+    }
+
+    let s = fs::read_to_string(&args.ifname)
+        .expect("cannot read input file");
+
+    // signed keys and doubles are sorted by their order-preserving keys
+    let res = match header.trim() {
+        "sequenceInt" => sort_ints(&args, s),
+        "sequenceDouble" => parse_keys::<f64>(&s).map(|arr| {
+            drop(s);
+            sort_and_write(&args, &arr);
+        }),
+        h => Err(format!("unsupported input type {h:?}")),
+    };
+    if let Err(e) = res {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -24,9 +24,62 @@
 // SOFTWARE.
 // ============================================================================
 
-use parlay::internal::integer_sort::integer_sort;
+use num_traits::{PrimInt, Unsigned};
+use parlay::internal::integer_sort::{integer_sort, OrderedKey};
 
-pub fn int_sort(inp: &[u32], bits: usize, dest: &mut Vec<u32>) {
-    let get_key = |x| x;
+/// An element of an integer sort input, sorted by its unsigned key.
+pub trait IntSortable: Copy + Send + Sync {
+    type Key: PrimInt + Unsigned + Send + Sync;
+
+    fn key(self) -> Self::Key;
+}
+
+impl IntSortable for u32 {
+    type Key = u32;
+    fn key(self) -> u32 { self }
+}
+
+impl IntSortable for u64 {
+    type Key = u64;
+    fn key(self) -> u64 { self }
+}
+
+// signed integers and doubles are sorted by their order-preserving keys
+macro_rules! int_sortable_ordered {
+    ($($t:ty),*) => {$(
+        impl IntSortable for $t {
+            type Key = <$t as OrderedKey>::Key;
+            fn key(self) -> Self::Key { self.ordered_key() }
+        }
+    )*};
+}
+
+int_sortable_ordered!(i32, i64, f64);
+
+// PBBS's pair<uint, uint> inputs: only the first element is the key
+impl IntSortable for (u32, u32) {
+    type Key = u32;
+    fn key(self) -> u32 { self.0 }
+}
+
+/// The narrowest type that holds every key of a `sequenceInt` input, whose
+/// keys are signed in PBBS but often fit in an unsigned type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType { U32, I32, U64, I64 }
+
+impl KeyType {
+    /// the type for the keys between `min` and `max`, if one holds them
+    pub fn of_range(min: i128, max: i128) -> Option<Self> {
+        let fits = |lo: i128, hi: i128| lo <= min && max <= hi;
+        if fits(0, u32::MAX as i128) { Some(Self::U32) }
+        else if fits(i32::MIN as i128, i32::MAX as i128) { Some(Self::I32) }
+        else if fits(0, u64::MAX as i128) { Some(Self::U64) }
+        else if fits(i64::MIN as i128, i64::MAX as i128) { Some(Self::I64) }
+        else { None }
+    }
+}
+
+pub fn int_sort<T: IntSortable>(inp: &[T], bits: usize, dest: &mut Vec<T>) {
+    let get_key = |x: T| x.key();
     integer_sort(inp, &get_key, bits, dest)
 }
@@ -0,0 +1,109 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::collections::{BTreeMap, BTreeSet};
+use clap::Parser;
+
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../common/words.rs"] mod words;
+use io::{chars_from_file, read_file_to_vec_seq};
+use words::fold;
+
+const DOC_TAG: &[u8] = b"<doc";
+const END_TAG: &[u8] = b"</doc";
+
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// ii results filename
+    #[clap(value_parser, required=true)]
+    rfname: String,
+
+    /// the input text's filename
+    #[clap(value_parser, required=true)]
+    ifname: String,
+}
+
+pub fn check(inp: &[u8], out: &[String]) -> bool {
+    let mut index: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
+    let n = inp.len();
+    // the current document, if inside one, and the number of documents seen
+    let (mut doc, mut docs): (Option<usize>, usize) = (None, 0);
+    let mut word = Vec::new();
+    let mut i = 0;
+    while i <= n {
+        if i < n && fold(inp[i]) != b' ' {
+            word.push(fold(inp[i]));
+            i += 1;
+            continue;
+        }
+        let w = String::from_utf8(std::mem::take(&mut word)).unwrap();
+        if let (false, Some(d)) = (w.is_empty(), doc) {
+            index.entry(w).or_default().insert(d);
+        }
+        if i < n && inp[i..].starts_with(END_TAG) { doc = None; }
+        if i < n && inp[i..].starts_with(DOC_TAG) {
+            doc = Some(docs);
+            docs += 1;
+            // skip the tag, up to its closing '>' or the next tag
+            i += DOC_TAG.len();
+            while i < n && inp[i] != b'>' && !inp[i..].starts_with(DOC_TAG) {
+                i += 1;
+            }
+            if i < n && inp[i] == b'>' { i += 1; }
+            continue;
+        }
+        i += 1;
+    }
+
+    if index.len() != out.len() {
+        eprintln!("expected {} words but found {}.", index.len(), out.len());
+        return false;
+    }
+
+    let diff_count = index
+        .iter()
+        .zip(out.iter())
+        .filter(|((w, ds), o)| {
+            let ds: Vec<_> = ds.iter().map(|d| d.to_string()).collect();
+            **o != format!("{w} {}", ds.join(" "))
+        }).count();
+    if diff_count != 0 {
+        eprintln!("output file has {diff_count} differences.");
+        false
+    } else { true }
+}
+
+fn main() {
+    let args = Args::parse();
+    let inp = chars_from_file(&args.ifname, false).unwrap();
+    let out: Vec<String> = read_file_to_vec_seq(&args.rfname)
+        .into_iter()
+        .filter(|l: &String| !l.is_empty())
+        .collect();
+    if check(&inp, &out) { println!("OK"); }
+    else { eprintln!("ERR"); std::process::exit(1); }
+}
@@ -0,0 +1,161 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::marker::PhantomData;
+use rayon::prelude::*;
+
+use parlay::Timer;
+use parlay::primitives::pack_index;
+use parlay::utilities::{hash64, hash_bytes};
+use parlay::internal::sample_sort_inplace;
+use parlay::internal::binary_search::binary_search;
+use parlay::internal::collect_reduce::{collect_reduce_sparse, HashEq, RCSHashEq};
+use crate::{DefChar, DefInt};
+use crate::words::{normalize, words};
+
+const DOC_TAG: &[u8] = b"<doc";
+const END_TAG: &[u8] = b"</doc";
+
+type Occurrence<'a> = (&'a [DefChar], DefInt);
+
+/// Reduces the occurrences of a word in a document to one of them.
+#[derive(Clone, Copy)]
+struct UniqueHelper<'a> { _o: PhantomData<Occurrence<'a>> }
+
+impl<'a> HashEq for UniqueHelper<'a> {
+    type IT = Occurrence<'a>;
+    type KT = Occurrence<'a>;
+    type RT = Occurrence<'a>;
+
+    fn hash(&self, (w, d): Self::KT) -> usize {
+        hash64(hash_bytes(w) ^ d as u64) as usize
+    }
+    fn get_key(&self, a: Self::IT) -> Self::KT { a }
+
+    fn get_key_mut<'b>(&'b self, a: &'b mut Self::RT) -> &'b mut Self::KT { a }
+
+    fn get_key_from_result(&self, a: Self::RT) -> Self::KT { a }
+    fn equal(&self, a: Self::KT, b: Self::KT) -> bool { a == b }
+}
+
+impl<'a> RCSHashEq for UniqueHelper<'a> {
+    type IT = Occurrence<'a>;
+    type KT = Occurrence<'a>;
+    type RT = Occurrence<'a>;
+
+    fn init(&self, _r: &mut Self::RT, _inp: Self::IT) {}
+    fn reduce(&self, s: &[Self::IT]) -> Self::RT { s[0] }
+    fn update(&self, _r: &mut Self::RT, _inp: Self::IT) {}
+}
+
+
+/// Builds the inverted index of the documents in `s`. Document `i` is the text
+/// after the `i`-th `<doc ...>` tag, up to the next `</doc` or `<doc`; the
+/// tags themselves and any text outside the documents are not indexed.
+/// Returns the words in sorted order, each with the sorted list of the
+/// documents that contain it.
+pub fn inverted_index(s: &[DefChar]) -> Vec<(String, Vec<DefInt>)> {
+    let mut t = Timer::new("ii"); //t.start();
+    let n = s.len();
+
+    // find the start of the documents
+    let doc_flags: Vec<bool> = (0..n)
+        .into_par_iter()
+        .map(|i| s[i..].starts_with(DOC_TAG))
+        .collect();
+    let mut doc_starts: Vec<usize> = vec![];
+    if n > 0 { pack_index(&doc_flags, &mut doc_starts); }
+
+    // and the range of their text, between the tags
+    let num_docs = doc_starts.len();
+    let texts: Vec<(usize, usize)> = (0..num_docs)
+        .into_par_iter()
+        .map(|i| {
+            let st = doc_starts[i] + DOC_TAG.len();
+            let en = if i + 1 == num_docs { n } else { doc_starts[i+1] };
+            let st = s[st.min(en)..en]
+                .iter()
+                .position(|&c| c == b'>')
+                .map_or(en, |p| st + p + 1);
+            let en = s[st..en]
+                .windows(END_TAG.len())
+                .position(|w| w == END_TAG)
+                .map_or(en, |p| st + p);
+            (st, en)
+        }).collect();
+    t.next("documents");
+
+    let normalized = normalize(s);
+    let words = words(&normalized);
+    let base = normalized.as_ptr() as usize;
+    let pairs: Vec<Occurrence> = words
+        .par_iter()
+        .filter_map(|&w| {
+            let pos = w.as_ptr() as usize - base;
+            let doc = binary_search(&doc_starts, pos + 1, |a, b| a < b);
+            if doc == 0 { return None; }
+            let (st, en) = texts[doc - 1];
+            if pos < st || pos >= en { return None; }
+            Some((w, (doc - 1) as DefInt))
+        }).collect();
+    t.next("tokens");
+
+    // remove repeated occurrences of a word in the same document
+    let mut uniq: Vec<Occurrence> = vec![];
+    collect_reduce_sparse(
+        &pairs,
+        UniqueHelper { _o: PhantomData },
+        &mut uniq
+    );
+    t.next("remove duplicates");
+
+    sample_sort_inplace(&mut uniq, |a, b| a < b, false);
+    t.next("sort");
+
+    // group the documents of each word
+    let m = uniq.len();
+    if m == 0 { return vec![]; }
+    let group_flags: Vec<bool> = (0..m)
+        .into_par_iter()
+        .map(|i| i == 0 || uniq[i].0 != uniq[i-1].0)
+        .collect();
+    let mut group_starts: Vec<usize> = vec![];
+    pack_index(&group_flags, &mut group_starts);
+    let res = (0..group_starts.len())
+        .into_par_iter()
+        .map(|g| {
+            let st = group_starts[g];
+            let en = if g + 1 == group_starts.len() { m }
+                else { group_starts[g+1] };
+            (
+                String::from_utf8(uniq[st].0.to_vec()).unwrap(),
+                uniq[st..en].iter().map(|p| p.1).collect()
+            )
+        }).collect();
+    t.next("group");
+
+    res
+}
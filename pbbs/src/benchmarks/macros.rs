@@ -107,3 +107,31 @@ macro_rules! finalize {
         println!("mean:  {:?}", $d);
     }
 }
+
+/// Exits if statistics are requested for an algorithm other than the given
+/// ones, which are the only ones that run speculative rounds. `--stats-file`
+/// implies `--stats`.
+#[macro_export]
+macro_rules! check_stats {
+    ($args: ident, $($alg: path),+) => {
+        $args.stats |= !$args.stats_file.is_empty();
+        if $args.stats && !matches!($args.algorithm, $($alg)|+) {
+            eprintln!("{} does not use speculative rounds.", $args.algorithm);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints the statistics, or writes them to `$file` if it is not empty.
+#[macro_export]
+macro_rules! print_stats {
+    ($file: ident, $st: ident) => {
+        if let Some(st) = $st {
+            if $file.is_empty() { println!("{st:#}"); }
+            else if let Err(e) = std::fs::write(&$file, format!("{st:#}\n")) {
+                eprintln!("cannot write {}: {e}", $file);
+                std::process::exit(1);
+            }
+        }
+    }
+}
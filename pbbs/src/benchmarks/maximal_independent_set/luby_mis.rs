@@ -0,0 +1,81 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
+use rayon::prelude::*;
+
+use parlay::random::Random;
+use crate::graph::Graph;
+
+
+// Luby's algorithm: in every round, the undecided vertices that have a
+// smaller random priority than all of their undecided neighbors join the
+// set, and their neighbors leave it. Flags are the same as serial_mis.
+pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
+    let n = g.n;
+    let flags: Vec<AtomicU8> = (0..n)
+        .into_par_iter()
+        .map(|_| AtomicU8::new(0))
+        .collect();
+    let mut live: Vec<u32> = (0..n as u32).into_par_iter().collect();
+    let mut r = Random::new(0);
+
+    while !live.is_empty() {
+        let pri = |v: usize| (r.ith_rand(v as u64), v);
+
+        let chosen: Vec<u32> = live
+            .par_iter()
+            .copied()
+            .filter(|&v| {
+                let (v, p) = (v as usize, pri(v as usize));
+                let vv = g.index(v);
+                vv.neighbors[..vv.degree].iter().all(|&w| {
+                    let w = w as usize;
+                    w == v || flags[w].load(Relaxed) != 0 || p < pri(w)
+                })
+            }).collect();
+
+        chosen
+            .par_iter()
+            .for_each(|&v| flags[v as usize].store(1, Relaxed));
+        chosen
+            .par_iter()
+            .for_each(|&v| {
+                let vv = g.index(v as usize);
+                for &w in &vv.neighbors[..vv.degree] {
+                    if w != v { flags[w as usize].store(2, Relaxed); }
+                }
+            });
+
+        live = live
+            .into_par_iter()
+            .filter(|&v| flags[v as usize].load(Relaxed) == 0)
+            .collect();
+        r = r.next();
+    }
+
+    flags.into_par_iter().map(|f| f.into_inner()).collect()
+}
@@ -36,48 +36,81 @@ use std::time::Duration;
 #[path ="../../common/graph_io.rs"] mod graph_io;
 
 use misc::*;
-use mis::{ serial_mis, rusty_incremental_mis};
+use mis::{
+    serial_mis,
+    rusty_incremental_mis,
+    luby_mis,
+    rootset_mis,
+    priority_dag_mis
+};
 use graph::Graph;
 use io::write_slice_to_file_seq;
 use graph_io::read_graph_from_file;
+use parlay::spec_for::SpecForStats;
 
-define_args!(Algs::RUSTINC);
+define_args!(
+    Algs::RUSTINC,
+    (stats, bool, false),
+    (stats_file, String, String::new())
+);
 
 define_algs!(
     (SERIAL, "serial"),
-    (RUSTINC, "rustinc")
+    (RUSTINC, "rustinc"),
+    (LUBY, "luby"),
+    (ROOTSET, "rootset"),
+    (PRIODAG, "priodag")
 );
 
-pub fn run(alg: Algs, rounds: usize, g: Graph) -> (Vec<u8>, Duration) {
+pub fn run(
+    alg: Algs,
+    rounds: usize,
+    g: Graph,
+    stats: bool
+) -> (Vec<u8>, Duration, Option<SpecForStats>) {
     let mis = match alg {
         Algs::SERIAL => serial_mis::maximal_independent_set,
         Algs::RUSTINC => rusty_incremental_mis::maximal_independent_set,
+        Algs::LUBY => luby_mis::maximal_independent_set,
+        Algs::ROOTSET => rootset_mis::maximal_independent_set,
+        Algs::PRIODAG => priority_dag_mis::maximal_independent_set,
     };
 
     let mut r = vec![];
+    let mut st = None;
 
     let mean = time_loop(
         "mis",
         rounds,
         Duration::new(1, 0),
         || {},
-        || { r = mis(&g); },
+        || {
+            if stats && alg == Algs::RUSTINC {
+                let (m, s) =
+                    rusty_incremental_mis::maximal_independent_set_stats(&g, true);
+                r = m;
+                st = Some(s);
+            } else { r = mis(&g); }
+        },
         || {}
     );
-    (r, mean)
+    (r, mean, st)
 }
 
 fn main() {
     init!();
 
-    let args = Args::parse();
+    let mut args = Args::parse();
+    check_stats!(args, Algs::RUSTINC);
     let g = read_graph_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, g);
+    let (r, d, st) = run(args.algorithm, args.rounds, g, args.stats);
 
+    let stats_file = args.stats_file.clone();
     finalize!(
         args,
         r,
         d,
         write_slice_to_file_seq(&r, args.ofname)
     );
+    print_stats!(stats_file, st);
 }
@@ -26,3 +26,6 @@
 
 pub(super) mod serial_mis;
 pub(super) mod rusty_incremental_mis;
+pub(super) mod luby_mis;
+pub(super) mod rootset_mis;
+pub(super) mod priority_dag_mis;
@@ -0,0 +1,82 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
+use rayon::prelude::*;
+
+use crate::graph::Graph;
+use super::rootset_mis::random_priorities;
+
+
+// The greedy MIS in random priority order, evaluated on the DAG that
+// orients every edge from the earlier endpoint to the later one. Every round,
+// an undecided vertex leaves the set if one of its earlier neighbors is in
+// it, and joins it if all of them are out. Same result as rootset_mis, with
+// more work but no counters. Flags are the same as serial_mis.
+pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
+    let n = g.n;
+    let pri = random_priorities(n);
+    let earlier = |a: usize, b: usize| (pri[a], a) < (pri[b], b);
+
+    let flags: Vec<AtomicU8> = (0..n)
+        .into_par_iter()
+        .map(|_| AtomicU8::new(0))
+        .collect();
+    let mut live: Vec<u32> = (0..n as u32).into_par_iter().collect();
+
+    while !live.is_empty() {
+        let decisions: Vec<u8> = live
+            .par_iter()
+            .map(|&v| {
+                let (v, vv) = (v as usize, g.index(v as usize));
+                let mut d = 1;
+                for &w in &vv.neighbors[..vv.degree] {
+                    let w = w as usize;
+                    if w == v || !earlier(w, v) { continue; }
+                    match flags[w].load(Relaxed) {
+                        0 => d = 0,
+                        1 => return 2,
+                        _ => {}
+                    }
+                }
+                d
+            }).collect();
+
+        live
+            .par_iter()
+            .zip(decisions.par_iter())
+            .for_each(|(&v, &d)| {
+                if d != 0 { flags[v as usize].store(d, Relaxed); }
+            });
+
+        live = live
+            .into_par_iter()
+            .filter(|&v| flags[v as usize].load(Relaxed) == 0)
+            .collect();
+    }
+
+    flags.into_par_iter().map(|f| f.into_inner()).collect()
+}
@@ -0,0 +1,95 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::atomic::{AtomicU8, AtomicU32, Ordering::Relaxed};
+use rayon::prelude::*;
+
+use parlay::random::Random;
+use crate::graph::Graph;
+
+
+/// random vertex priorities; `a` comes before `b` if
+/// `(pri[a], a) < (pri[b], b)`.
+pub(super) fn random_priorities(n: usize) -> Vec<u64> {
+    let r = Random::new(0);
+    (0..n)
+        .into_par_iter()
+        .map(|v| r.ith_rand(v as u64))
+        .collect()
+}
+
+// The greedy MIS in random priority order, computed from the root set: the
+// undecided vertices with no undecided earlier neighbor. Every round, the
+// roots join the set, their neighbors leave it, and the later neighbors of
+// the vertices that left become roots once they run out of earlier
+// neighbors. Flags are the same as serial_mis.
+pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
+    let n = g.n;
+    let pri = random_priorities(n);
+    let earlier = |a: usize, b: usize| (pri[a], a) < (pri[b], b);
+    let nghs = |v: usize| {
+        let vv = g.index(v);
+        vv.neighbors[..vv.degree]
+            .iter()
+            .map(|&w| w as usize)
+            .filter(move |&w| w != v)
+    };
+
+    let flags: Vec<AtomicU8> = (0..n)
+        .into_par_iter()
+        .map(|_| AtomicU8::new(0))
+        .collect();
+    let counts: Vec<AtomicU32> = (0..n)
+        .into_par_iter()
+        .map(|v| AtomicU32::new(nghs(v).filter(|&w| earlier(w, v)).count() as u32))
+        .collect();
+    let (flags, counts) = (&flags, &counts);
+    let mut roots: Vec<usize> = (0..n)
+        .into_par_iter()
+        .filter(|&v| counts[v].load(Relaxed) == 0)
+        .collect();
+
+    while !roots.is_empty() {
+        roots.par_iter().for_each(|&v| flags[v].store(1, Relaxed));
+
+        // each removed vertex is claimed by exactly one root
+        let removed: Vec<usize> = roots
+            .par_iter()
+            .flat_map_iter(|&v| nghs(v).filter(|&w| {
+                flags[w].compare_exchange(0, 2, Relaxed, Relaxed).is_ok()
+            })).collect();
+
+        roots = removed
+            .par_iter()
+            .flat_map_iter(|&w| nghs(w).filter(move |&u| {
+                earlier(w, u)
+                    && flags[u].load(Relaxed) == 0
+                    && counts[u].fetch_sub(1, Relaxed) == 1
+            })).collect();
+    }
+
+    flags.par_iter().map(|f| f.load(Relaxed)).collect()
+}
@@ -29,8 +29,7 @@ use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
 use rayon::prelude::*;
 
 use crate::graph::Graph;
-#[path="../../common/spec_for.rs"] mod spec_for;
-use spec_for::StatefulSpecFor;
+use parlay::spec_for::{SpecFor, SpecForStats};
 
 
 #[derive(Clone)]
@@ -39,6 +38,14 @@ struct MISState {
 }
 
 pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
+    maximal_independent_set_stats(g, false).0
+}
+
+/// also returns the statistics of the speculative loop
+pub fn maximal_independent_set_stats(
+    g: &Graph,
+    detailed: bool
+) -> (Vec<u8>, SpecForStats) {
     let n = g.n;
     #[cfg(not(feature = "AW_safe"))]
     let flags: Vec<u8> = (0..n)
@@ -79,17 +86,16 @@ pub fn maximal_independent_set(g: &Graph) -> Vec<u8> {
         s.flag > 0
     };
 
-    (0..n).stateful_spec_for(
-        reserve,
-        commit,
-        MISState { flag: 0 },
-        20,
-        Some(64),
-        Some(256)
-    ).expect("failed speculative for");
+    let stats = SpecFor::new()
+        .granularity(20)
+        .reserve_chunk(64)
+        .commit_chunk(256)
+        .detailed_stats(detailed)
+        .run_stateful(&(0..n), reserve, commit, MISState { flag: 0 })
+        .expect("failed speculative for");
 
     #[cfg(not(feature = "AW_safe"))]
-    return flags;
+    return (flags, stats);
     #[cfg(feature = "AW_safe")]
-    return flags.into_par_iter().map(|f| f.load(Relaxed)).collect();
+    return (flags.into_par_iter().map(|f| f.load(Relaxed)).collect(), stats);
 }
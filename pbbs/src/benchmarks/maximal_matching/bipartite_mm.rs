@@ -0,0 +1,53 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use crate::DefInt;
+use crate::graph::EdgeArray;
+use super::rusty_incremental_mm::greedy_matching;
+
+use parlay::spec_for::SpecForStats;
+
+
+// Maximal matching of a bipartite graph: for every edge, `u` is one of the
+// `num_rows` left vertices and `v` one of the `num_cols` right vertices, so
+// `(i, j)` and `(j, i)` are different edges that do not share endpoints.
+pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
+    maximal_matching_stats(ea, false).0
+}
+
+/// also returns the statistics of the speculative loop
+pub fn maximal_matching_stats(
+    ea: &EdgeArray,
+    detailed: bool
+) -> (Vec<DefInt>, SpecForStats) {
+    let rows = ea.num_rows;
+    greedy_matching(
+        rows + ea.num_cols,
+        ea.non_zeros,
+        |i| (ea[i].u as usize, rows + ea[i].v as usize),
+        detailed
+    )
+}
@@ -24,11 +24,18 @@
 // SOFTWARE.
 // ============================================================================
 
+#![allow(dead_code)]
+
 use clap::Parser;
 
-use pbbs::common::graph::EdgeArray;
-use pbbs::common::io::read_file_to_vec_seq;
-use pbbs::common::graph_io::read_edge_array_from_file;
+#[path ="../../misc.rs"] mod misc;
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../common/graph.rs"] mod graph;
+#[path ="../../common/graph_io.rs"] mod graph_io;
+
+use misc::*;
+use io::read_file_to_vec_seq;
+use graph_io::{read_edge_array_from_file, read_wgh_edge_array_from_file};
 
 #[derive(Parser, Debug)]
 #[clap(version, about, long_about = None)]
@@ -40,22 +47,45 @@ struct Args {
     /// the input graph's filename
     #[clap(value_parser, required=true)]
     ifname: String,
+
+    /// the input is a WeightedEdgeArray and the matching must be greedy
+    #[clap(long, value_parser, required=false, default_value_t=false)]
+    weighted: bool,
+
+    /// the rows and the columns of the input are separate vertices
+    #[clap(long, value_parser, required=false, default_value_t=false)]
+    bipartite: bool,
 }
 
-pub fn check(ea: EdgeArray, matching: &[usize]) -> bool {
-    let m = ea.non_zeros;
-    let n = std::cmp::max(ea.num_rows, ea.num_cols);
+/// Checks that `matching` is a maximal matching of the `m` edges over `n`
+/// vertices, where `endpoints(i)` gives the endpoints of edge `i`. With
+/// `weights`, every unmatched edge must also share an endpoint with a
+/// matched edge at least as heavy, as the greedy algorithm guarantees.
+pub fn check<E>(
+    n: usize,
+    m: usize,
+    endpoints: E,
+    weights: Option<&[DefFloat]>,
+    matching: &[usize]
+) -> bool
+where
+    E: Fn(usize) -> (usize, usize)
+{
     let mut vs = vec![usize::MAX; n];
     let mut flags = vec![false; m];
 
-    matching.iter().for_each(|&i| {
-        (vs[ea[i].u as usize], vs[ea[i].v as usize]) = (i, i);
+    for &i in matching {
+        if i >= m || flags[i] {
+            println!("mm_check: invalid or repeated edge {i}");
+            return false;
+        }
+        let (u, v) = endpoints(i);
+        (vs[u], vs[v]) = (i, i);
         flags[i] = true;
-    });
+    }
 
     for i in 0..m {
-        let u = ea[i].u as usize;
-        let v = ea[i].v as usize;
+        let (u, v) = endpoints(i);
         if flags[i] {
             if vs[u] != i {
                 println!("mm_check: edges share vertex {u}");
@@ -65,9 +95,21 @@ pub fn check(ea: EdgeArray, matching: &[usize]) -> bool {
                 println!("mm_check: edges share vertex {v}");
                 return false;
             }
-        } else {
-            if u != v && vs[u] == usize::MAX && vs[v] == usize::MAX {
-                println!("mm_check: neither endpoint matched for edge {i}"); return false;
+        } else if u != v {
+            if vs[u] == usize::MAX && vs[v] == usize::MAX {
+                println!("mm_check: neither endpoint matched for edge {i}");
+                return false;
+            }
+            if let Some(w) = weights {
+                let heaviest = [vs[u], vs[v]]
+                    .into_iter()
+                    .filter(|&j| j != usize::MAX)
+                    .map(|j| w[j])
+                    .fold(DefFloat::NEG_INFINITY, DefFloat::max);
+                if heaviest < w[i] {
+                    println!("mm_check: edge {i} is heavier than its matched neighbors");
+                    return false;
+                }
             }
         }
     }
@@ -76,8 +118,31 @@ pub fn check(ea: EdgeArray, matching: &[usize]) -> bool {
 
 fn main() {
     let args = Args::parse();
-    let ea = read_edge_array_from_file(&args.ifname);
     let r: Vec<usize> = read_file_to_vec_seq(&args.rfname);
-    if check(ea, &r) { println!("OK"); }
+
+    let ok = if args.weighted {
+        let wea = read_wgh_edge_array_from_file(&args.ifname);
+        let w: Vec<DefFloat> = wea.es.iter().map(|e| e.w).collect();
+        check(
+            wea.n,
+            wea.m,
+            |i| (wea[i].u as usize, wea[i].v as usize),
+            Some(&w),
+            &r
+        )
+    } else {
+        let ea = read_edge_array_from_file(&args.ifname);
+        let rows = if args.bipartite { ea.num_rows } else { 0 };
+        check(
+            if args.bipartite { ea.num_rows + ea.num_cols }
+            else { std::cmp::max(ea.num_rows, ea.num_cols) },
+            ea.non_zeros,
+            |i| (ea[i].u as usize, rows + ea[i].v as usize),
+            None,
+            &r
+        )
+    };
+
+    if ok { println!("OK"); }
     else { println!("ERR"); std::process::exit(1); }
 }
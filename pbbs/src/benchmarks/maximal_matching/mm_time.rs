@@ -36,48 +36,96 @@ use std::time::Duration;
 #[path ="../../common/graph_io.rs"] mod graph_io;
 
 use misc::*;
-use graph::EdgeArray;
 use io::write_slice_to_file_seq;
-use graph_io::read_edge_array_from_file;
-use mm::{ rusty_incremental_mm, serial_mm };
+use graph_io::{read_edge_array_from_file, read_wgh_edge_array_from_file};
+use parlay::spec_for::SpecForStats;
+use mm::{ rusty_incremental_mm, serial_mm, weighted_mm, bipartite_mm };
 
-define_args!(Algs::RUSTINC);
+define_args!(
+    Algs::RUSTINC,
+    (stats, bool, false),
+    (stats_file, String, String::new())
+);
 
 define_algs!(
     (SERIAL, "serial"),
-    (RUSTINC, "rustinc")
+    (RUSTINC, "rustinc"),
+    (WEIGHTED, "weighted"),
+    (BIPARTITE, "bipartite")
 );
 
-pub fn run(alg: Algs, rounds: usize, ea: EdgeArray) -> (Vec<DefInt>, Duration) {
-    let mm = match alg {
-        Algs::SERIAL    =>  serial_mm::maximal_matching,
-        Algs::RUSTINC   =>  rusty_incremental_mm::maximal_matching,
-    };
+type MMStats<E> = fn(&E, bool) -> (Vec<DefInt>, SpecForStats);
 
+pub fn run<E>(
+    rounds: usize,
+    ea: &E,
+    mm: fn(&E) -> Vec<DefInt>,
+    mm_stats: Option<MMStats<E>>,
+    stats: bool
+) -> (Vec<DefInt>, Duration, Option<SpecForStats>) {
     let mut r = vec![];
+    let mut st = None;
 
     let mean = time_loop(
         "mm",
         rounds,
         Duration::new(1, 0),
         || {},
-        || { r = mm(&ea); },
+        || match mm_stats {
+            Some(mm_stats) if stats => {
+                let (m, s) = mm_stats(ea, true);
+                r = m;
+                st = Some(s);
+            },
+            _ => { r = mm(ea); }
+        },
         || {}
     );
-    (r, mean)
+    (r, mean, st)
 }
 
 fn main() {
     init!();
 
-    let args = Args::parse();
-    let g = read_edge_array_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, g);
+    let mut args = Args::parse();
+    check_stats!(args, Algs::RUSTINC, Algs::WEIGHTED, Algs::BIPARTITE);
+    let (r, d, st) = match args.algorithm {
+        Algs::SERIAL => run(
+            args.rounds,
+            &read_edge_array_from_file(&args.ifname),
+            serial_mm::maximal_matching,
+            None,
+            args.stats
+        ),
+        Algs::RUSTINC => run(
+            args.rounds,
+            &read_edge_array_from_file(&args.ifname),
+            rusty_incremental_mm::maximal_matching,
+            Some(rusty_incremental_mm::maximal_matching_stats),
+            args.stats
+        ),
+        Algs::WEIGHTED => run(
+            args.rounds,
+            &read_wgh_edge_array_from_file(&args.ifname),
+            weighted_mm::maximal_matching,
+            Some(weighted_mm::maximal_matching_stats),
+            args.stats
+        ),
+        Algs::BIPARTITE => run(
+            args.rounds,
+            &read_edge_array_from_file(&args.ifname),
+            bipartite_mm::maximal_matching,
+            Some(bipartite_mm::maximal_matching_stats),
+            args.stats
+        ),
+    };
 
+    let stats_file = args.stats_file.clone();
     finalize!(
         args,
         r,
         d,
         write_slice_to_file_seq(&r, args.ofname)
     );
+    print_stats!(stats_file, st);
 }
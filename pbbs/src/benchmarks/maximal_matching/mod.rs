@@ -26,3 +26,5 @@
 
 pub(crate) mod serial_mm;
 pub(crate) mod rusty_incremental_mm;
+pub(crate) mod weighted_mm;
+pub(crate) mod bipartite_mm;
@@ -33,14 +33,39 @@ use crate::{graph::EdgeArray};
 #[cfg(feature = "AW_safe")]
 use crate::ORDER;
 
-#[path="../../common/spec_for.rs"] mod spec_for;
 
-use spec_for::{SpecFor, Reservation};
+use parlay::spec_for::{SpecFor, SpecForStats, Reservation};
 
 
 pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
-    let n = std::cmp::max(ea.num_rows, ea.num_cols);
-    let m = ea.non_zeros;
+    maximal_matching_stats(ea, false).0
+}
+
+/// also returns the statistics of the speculative loop
+pub fn maximal_matching_stats(
+    ea: &EdgeArray,
+    detailed: bool
+) -> (Vec<DefInt>, SpecForStats) {
+    greedy_matching(
+        std::cmp::max(ea.num_rows, ea.num_cols),
+        ea.non_zeros,
+        |i| (ea[i].u as usize, ea[i].v as usize),
+        detailed
+    )
+}
+
+/// The greedy matching that visits the edges `0..m` in order, where
+/// `endpoints(i)` gives the two endpoints (in `0..n`) of edge `i`. Returns
+/// the positions of the matched edges.
+pub(super) fn greedy_matching<E>(
+    n: usize,
+    m: usize,
+    endpoints: E,
+    detailed: bool
+) -> (Vec<DefInt>, SpecForStats)
+where
+    E: Fn(usize) -> (usize, usize) + Sync
+{
     let rs: Vec<Reservation> = (0..n)
         .into_par_iter()
         .map(|_| Reservation::new())
@@ -61,7 +86,7 @@ pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
     let matched_ptr = matched.as_ptr() as usize;
 
     let reserve = |i: usize| -> bool {
-        let (u, v) = (ea[i].u as usize, ea[i].v as usize);
+        let (u, v) = endpoints(i);
         let i = i as u32;
         #[cfg(not(feature = "AW_safe"))]
         if matched[u] || matched[v] || u == v { false }
@@ -81,7 +106,7 @@ pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
     };
 
     let commit = |i: usize| -> bool {
-        let (u, v) = (ea[i].u as usize, ea[i].v as usize);
+        let (u, v) = endpoints(i);
         let i = i as u32;
         if rs[v].check(i) {
             rs[v].reset();
@@ -103,13 +128,13 @@ pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
         return false;
     };
 
-    (0..m).spec_for(
-        reserve,
-        commit,
-        10,
-        Some(1024),
-        Some(2048)
-    ).unwrap();
+    let stats = SpecFor::new()
+        .granularity(10)
+        .reserve_chunk(1024)
+        .commit_chunk(2048)
+        .detailed_stats(detailed)
+        .run(&(0..m), reserve, commit)
+        .unwrap();
 
     let mut matching_idx = vec![];
     parlay::primitives::pack(
@@ -124,5 +149,5 @@ pub fn maximal_matching(ea: &EdgeArray) -> Vec<DefInt> {
         &mut matching_idx
     );
 
-    matching_idx
+    (matching_idx, stats)
 }
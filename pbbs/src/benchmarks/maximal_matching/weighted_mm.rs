@@ -0,0 +1,64 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use crate::DefInt;
+use crate::graph::WghEdgeArray;
+use super::rusty_incremental_mm::greedy_matching;
+
+use parlay::spec_for::SpecForStats;
+
+
+// Greedy maximal-weight matching: the edges are visited from the heaviest
+// to the lightest (ties broken by index), which gives the same matching as
+// the sequential greedy algorithm and at least half of the optimal weight.
+pub fn maximal_matching(wea: &WghEdgeArray) -> Vec<DefInt> {
+    maximal_matching_stats(wea, false).0
+}
+
+/// also returns the statistics of the speculative loop
+pub fn maximal_matching_stats(
+    wea: &WghEdgeArray,
+    detailed: bool
+) -> (Vec<DefInt>, SpecForStats) {
+    let mut order: Vec<usize> = (0..wea.m).into_par_iter().collect();
+    order.par_sort_unstable_by(|&a, &b| {
+        wea[b].w.total_cmp(&wea[a].w).then(a.cmp(&b))
+    });
+
+    let (mut matching, stats) = greedy_matching(
+        wea.n,
+        wea.m,
+        |i| (wea[order[i]].u as usize, wea[order[i]].v as usize),
+        detailed
+    );
+    matching
+        .par_iter_mut()
+        .for_each(|i| *i = order[*i as usize] as DefInt);
+
+    (matching, stats)
+}
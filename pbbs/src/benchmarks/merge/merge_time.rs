@@ -0,0 +1,95 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+#[path ="mod.rs"] mod merge;
+#[path ="../macros.rs"] mod macros;
+#[path ="../../common/io.rs"] mod io;
+
+use std::time::Duration;
+use io::{read_big_file_to_vec, write_slice_to_file_seq};
+
+define_args!(
+    Algs::KWAY,
+    (runs, usize, 16)
+);
+
+define_algs!(
+    (KWAY,  "kway"),
+    (RAYON, "rayon")
+);
+
+pub fn run(
+    alg: Algs,
+    rounds: usize,
+    runs: &[&[u32]]
+) -> (Vec<u32>, Duration)
+{
+    let f = match alg {
+        Algs::KWAY  => merge::kway::merge_runs,
+        Algs::RAYON => merge::rayon::merge_runs,
+    };
+
+    let mut r = vec![];
+    let mean = time_loop(
+        "merge",
+        rounds,
+        Duration::new(1, 0),
+        || {},
+        || { f(runs, &mut r); },
+        || {}
+    );
+
+    (r, mean)
+}
+
+fn main() {
+    init!();
+
+    let args = Args::parse();
+    let mut arr: Vec<u32> = Vec::new();
+    read_big_file_to_vec(
+        &args.ifname,
+        Some(|w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")}),
+        &mut arr
+    );
+
+    // cut the input into equal runs and sort each one (not timed)
+    let k = args.runs.max(1);
+    let run_size = arr.len().div_ceil(k).max(1);
+    arr.par_chunks_mut(run_size).for_each(|c| c.sort_unstable());
+    let runs: Vec<&[u32]> = arr.chunks(run_size).collect();
+
+    let (r, d) = run(args.algorithm, args.rounds, &runs);
+
+    finalize!(
+        args,
+        r,
+        d,
+        write_slice_to_file_seq(&r, args.ofname)
+    );
+}
@@ -0,0 +1,125 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering::Relaxed};
+use rayon::prelude::*;
+
+use parlay::primitives::pack_index;
+use crate::DefInt;
+use crate::graph::WghEdgeArray;
+
+
+// Parallel Borůvka: every round, each vertex picks its lightest edge, the
+// picked edges join the forest and their endpoints are contracted into stars
+// by pointer jumping. The edges are then relabeled with the star centers and
+// the self loops dropped, so the number of vertices at least halves every
+// round.
+pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
+    let (n, m) = (wea.n, wea.m);
+
+    // sorted by (weight, id) so that an edge's position is a distinct
+    // priority, which no later round invalidates since filtering keeps the
+    // order.
+    let mut order: Vec<usize> = (0..m).into_par_iter().collect();
+    order.par_sort_unstable_by(|&a, &b| {
+        wea[a].w.total_cmp(&wea[b].w).then(a.cmp(&b))
+    });
+    let mut es: Vec<(DefInt, DefInt, DefInt)> = order
+        .into_par_iter()
+        .map(|i| (wea[i].u, wea[i].v, i as DefInt))
+        .filter(|e| e.0 != e.1)
+        .collect();
+
+    let min_edge: Vec<AtomicU32> = (0..n)
+        .into_par_iter()
+        .map(|_| AtomicU32::new(u32::MAX))
+        .collect();
+    let parents: Vec<AtomicU32> = (0..n as u32)
+        .into_par_iter()
+        .map(AtomicU32::new)
+        .collect();
+    let msf_flags: Vec<AtomicBool> = (0..m)
+        .into_par_iter()
+        .map(|_| AtomicBool::new(false))
+        .collect();
+    let mut vs: Vec<DefInt> = (0..n as DefInt).into_par_iter().collect();
+
+    while !es.is_empty() {
+        es.par_iter().enumerate().for_each(|(j, &(u, v, _))| {
+            min_edge[u as usize].fetch_min(j as u32, Relaxed);
+            min_edge[v as usize].fetch_min(j as u32, Relaxed);
+        });
+        vs = vs
+            .into_par_iter()
+            .filter(|&v| min_edge[v as usize].load(Relaxed) != u32::MAX)
+            .collect();
+
+        // hook onto the other endpoint of the lightest edge; of two vertices
+        // that picked the same edge, the smaller one becomes the center.
+        vs.par_iter().for_each(|&v| {
+            let j = min_edge[v as usize].load(Relaxed) as usize;
+            let (a, b, id) = es[j];
+            let u = if a == v { b } else { a };
+            if v < u && min_edge[u as usize].load(Relaxed) == j as u32 {
+                return;
+            }
+            parents[v as usize].store(u, Relaxed);
+            msf_flags[id as usize].store(true, Relaxed);
+        });
+
+        // pointer jumping until every vertex points to its star's center
+        while vs
+            .par_iter()
+            .filter(|&&v| {
+                let p = parents[v as usize].load(Relaxed);
+                let gp = parents[p as usize].load(Relaxed);
+                if p != gp { parents[v as usize].store(gp, Relaxed); }
+                p != gp
+            }).count() > 0 {}
+
+        es = es
+            .into_par_iter()
+            .map(|(u, v, id)| (
+                parents[u as usize].load(Relaxed),
+                parents[v as usize].load(Relaxed),
+                id
+            ))
+            .filter(|e| e.0 != e.1)
+            .collect();
+
+        vs.par_iter().for_each(|&v| min_edge[v as usize].store(u32::MAX, Relaxed));
+        vs = vs
+            .into_par_iter()
+            .filter(|&v| parents[v as usize].load(Relaxed) == v)
+            .collect();
+    }
+
+    let msf_flags: Vec<bool> = msf_flags
+        .into_par_iter()
+        .map(|f| f.into_inner())
+        .collect();
+    pack_index(&msf_flags, dest);
+}
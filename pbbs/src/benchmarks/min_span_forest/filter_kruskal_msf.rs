@@ -0,0 +1,114 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use parlay::random::Random;
+use parlay::primitives::pack_index;
+use crate::{DefInt, DefIntS};
+use crate::graph::WghEdgeArray;
+use crate::msf::serial_msf::IndexedEdge;
+use crate::union_find::AtomicUnionFind;
+
+const BASE_CASE_SIZE: usize = 1 << 13;
+const SAMPLE_SIZE: usize = 101;
+
+
+#[inline(always)]
+fn less(a: &IndexedEdge, b: &IndexedEdge) -> bool {
+    if a.w == b.w { a.id < b.id }
+    else { a.w < b.w }
+}
+
+fn kruskal(
+    es: &mut [IndexedEdge],
+    uf: &AtomicUnionFind,
+    msf_flags: &mut [bool]
+) {
+    es.par_sort_unstable_by(|a, b| {
+        a.w.total_cmp(&b.w).then(a.id.cmp(&b.id))
+    });
+    for e in es.iter() {
+        if uf.unite(e.u as DefIntS, e.v as DefIntS) {
+            msf_flags[e.id as usize] = true;
+        }
+    }
+}
+
+fn filter_kruskal(
+    mut es: Vec<IndexedEdge>,
+    uf: &AtomicUnionFind,
+    msf_flags: &mut [bool],
+    r: Random
+) {
+    if es.len() <= BASE_CASE_SIZE {
+        kruskal(&mut es, uf, msf_flags);
+        return;
+    }
+
+    let mut sample: Vec<IndexedEdge> = (0..SAMPLE_SIZE)
+        .map(|i| es[r.ith_rand(i as u64) as usize % es.len()])
+        .collect();
+    sample.sort_unstable_by(|a, b| {
+        a.w.total_cmp(&b.w).then(a.id.cmp(&b.id))
+    });
+    let pivot = sample[SAMPLE_SIZE / 2];
+
+    let (light, mut heavy): (Vec<_>, Vec<_>) = es
+        .into_par_iter()
+        .partition(|e| less(e, &pivot));
+    if light.is_empty() {
+        kruskal(&mut heavy, uf, msf_flags);
+        return;
+    }
+    filter_kruskal(light, uf, msf_flags, r.fork(0));
+
+    // only the heavy edges between different trees can still join the forest
+    let heavy: Vec<_> = heavy
+        .into_par_iter()
+        .filter(|e| uf.find(e.u as DefIntS) != uf.find(e.v as DefIntS))
+        .collect();
+    filter_kruskal(heavy, uf, msf_flags, r.fork(1));
+}
+
+// Filter-Kruskal: the edges are split around a pivot weight taken from a
+// random sample, the light half is solved recursively, and the heavy edges
+// whose endpoints got connected are filtered out before the heavy half is
+// solved, so most heavy edges are never sorted.
+pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
+    let (n, m) = (wea.n, wea.m);
+    let es: Vec<_> = (0..m)
+        .into_par_iter()
+        .map(|i| IndexedEdge::new(wea[i], i as DefInt))
+        .filter(|e| e.u != e.v)
+        .collect();
+
+    let uf = AtomicUnionFind::new(n);
+    let mut msf_flags = vec![false; m];
+    filter_kruskal(es, &uf, &mut msf_flags, Random::new(0));
+
+    pack_index(&msf_flags, dest);
+}
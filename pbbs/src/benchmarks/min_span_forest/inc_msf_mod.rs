@@ -39,11 +39,19 @@ use crate::union_find::AtomicUnionFind;
 #[cfg(feature = "AW_safe")]
 use crate::ORDER;
 
-#[path="../../common/spec_for.rs"] mod spec_for;
-use spec_for::{SpecFor, Reservation};
+use parlay::spec_for::{SpecFor, SpecForStats, Reservation};
 
 
 pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
+    minimum_spanning_forest_stats(wea, dest, false);
+}
+
+/// also returns the statistics of the speculative loop
+pub fn minimum_spanning_forest_stats(
+    wea: &WghEdgeArray,
+    dest: &mut Vec<DefInt>,
+    detailed: bool
+) -> SpecForStats {
     #[cfg(feature = "AW_safe")]
     eprintln!("WARNING: AW_safe is enabled, \
         but this algorithm has an array that requires synchronization.");
@@ -93,8 +101,8 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         e.u = luf.find(e.u as DefIntS) as DefInt;
         e.v = luf.find(e.v as DefIntS) as DefInt;
         if e.u != e.v {
-            rs[e.v as usize].reserve(idxs[i]);
-            rs[e.u as usize].reserve(idxs[i]);
+            rs[e.v as usize].reserve(i as DefInt);
+            rs[e.u as usize].reserve(i as DefInt);
             true
         } else { false }
     };
@@ -104,13 +112,13 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         let luf = unsafe { (_uf_ptr as *mut UnionFind).as_mut().unwrap() };
         let e = wea[idxs[i] as usize];
         let (u, v) = (e.u, e.v);
-        if rs[v as usize].check(idxs[i]) {
-            rs[u as usize].check_reset(idxs[i]);
+        if rs[v as usize].check(i as DefInt) {
+            rs[u as usize].check_reset(i as DefInt);
             luf.link(v as DefIntS, u as DefIntS);
             unsafe { (_msf_flags_ptr as *mut bool)
                 .add(idxs[i] as usize).write(true); }
             true
-        } else if rs[u as usize].check(idxs[i]) {
+        } else if rs[u as usize].check(i as DefInt) {
             luf.link(u as DefIntS, v as DefIntS);
             unsafe { (_msf_flags_ptr as *mut bool)
                 .add(idxs[i] as usize).write(true);}
@@ -125,12 +133,12 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         let luf = &uf;
         let e = wea[idxs[i] as usize];
         let (u, v) = (e.u, e.v);
-        if rs[v as usize].check(idxs[i]) {
-            rs[u as usize].check_reset(idxs[i]);
+        if rs[v as usize].check(i as DefInt) {
+            rs[u as usize].check_reset(i as DefInt);
             luf.link(v as DefIntS, u as DefIntS);
             msf_flags[idxs[i] as usize].store(true, ORDER);
             true
-        } else if rs[u as usize].check(idxs[i]) {
+        } else if rs[u as usize].check(i as DefInt) {
             luf.link(u as DefIntS, v as DefIntS);
             msf_flags[idxs[i] as usize].store(true, ORDER);
             true
@@ -139,10 +147,13 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         }
     };
 
-    let (rcs, ccs) = (Some(1024), Some(2048));
     t.next("Initializations");
-    (0..m)
-        .spec_for(reserve, commit, 20, rcs, ccs)
+    let stats = SpecFor::new()
+        .granularity(20)
+        .reserve_chunk(1024)
+        .commit_chunk(2048)
+        .detailed_stats(detailed)
+        .run(&(0..m), reserve, commit)
         .expect("failed speculative for");
     t.next("Specualtive For");
 
@@ -153,4 +164,5 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         .collect();
     pack_index(&msf_flags, dest);
     t.next("Packing");
+    stats
 }
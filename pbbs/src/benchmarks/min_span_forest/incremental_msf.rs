@@ -40,8 +40,7 @@ use crate::union_find::UnionFind;
 #[cfg(feature = "AW_safe")]
 use crate::union_find::AtomicUnionFind;
 
-#[path="../../common/spec_for.rs"] mod spec_for;
-use spec_for::{SpecFor, Reservation};
+use parlay::spec_for::{SpecFor, SpecForStats, Reservation};
 
 
 #[inline(always)]
@@ -51,6 +50,15 @@ fn cmp_idx_edge(a: IndexedEdge, b: IndexedEdge) -> bool {
 }
 
 pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
+    minimum_spanning_forest_stats(wea, dest, false);
+}
+
+/// also returns the statistics of the speculative loop
+pub fn minimum_spanning_forest_stats(
+    wea: &WghEdgeArray,
+    dest: &mut Vec<DefInt>,
+    detailed: bool
+) -> SpecForStats {
     #[cfg(feature = "AW_safe")]
     eprintln!("WARNING: AW_safe is enabled, \
         but this algorithm has an array that requires synchronization.");
@@ -153,11 +161,14 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
         }
     };
 
-    let (rcs, ccs) = (Some(1024), Some(2048));
     t.next("Initializations");
-    
-    (0..iwea.len())
-        .spec_for(reserve, commit, 20, rcs, ccs)
+
+    let stats = SpecFor::new()
+        .granularity(20)
+        .reserve_chunk(1024)
+        .commit_chunk(2048)
+        .detailed_stats(detailed)
+        .run(&(0..iwea.len()), reserve, commit)
         .expect("failed speculative for");
     t.next("Specualtive For");
 
@@ -169,4 +180,5 @@ pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
 
     pack_index(&msf_flags, dest);
     t.next("Packing");
+    stats
 }
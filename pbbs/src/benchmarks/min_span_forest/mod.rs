@@ -27,3 +27,5 @@
 pub mod serial_msf;
 pub mod inc_msf_mod;
 pub mod incremental_msf;
+pub mod boruvka_msf;
+pub mod filter_kruskal_msf;
@@ -0,0 +1,119 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+use clap::Parser;
+
+#[path ="../../misc.rs"] mod misc;
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../common/graph.rs"] mod graph;
+#[path ="../../common/graph_io.rs"] mod graph_io;
+#[path ="../../algorithm/union_find.rs"] mod union_find;
+
+use misc::*;
+use graph::WghEdgeArray;
+use io::read_file_to_vec_seq;
+use graph_io::read_wgh_edge_array_from_file;
+use union_find::UnionFind;
+
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// msf results filename
+    #[clap(value_parser, required=true)]
+    rfname: String,
+
+    /// the input graph's filename
+    #[clap(value_parser, required=true)]
+    ifname: String,
+}
+
+/// the weight of the minimum spanning forest found by a serial Kruskal
+fn kruskal_weight(wea: &WghEdgeArray) -> f64 {
+    let mut es: Vec<usize> = (0..wea.m).collect();
+    es.sort_by(|&a, &b| wea[a].w.total_cmp(&wea[b].w));
+
+    let mut uf = UnionFind::new(wea.n);
+    let mut weight = 0.0;
+    for i in es {
+        let u = uf.find(wea[i].u as DefIntS);
+        let v = uf.find(wea[i].v as DefIntS);
+        if u != v {
+            uf.union_roots(u, v);
+            weight += wea[i].w as f64;
+        }
+    }
+    weight
+}
+
+pub fn check(wea: &WghEdgeArray, out: &[usize]) -> bool {
+    let mut flags = vec![false; wea.m];
+    let mut uf = UnionFind::new(wea.n);
+    let mut weight = 0.0;
+
+    for &i in out {
+        if i >= wea.m || flags[i] {
+            println!("msf_check: invalid or repeated edge {i}");
+            return false;
+        }
+        flags[i] = true;
+
+        let u = uf.find(wea[i].u as DefIntS);
+        let v = uf.find(wea[i].v as DefIntS);
+        if u == v {
+            println!("msf_check: edge {i} closes a cycle");
+            return false;
+        }
+        uf.union_roots(u, v);
+        weight += wea[i].w as f64;
+    }
+
+    for i in 0..wea.m {
+        let u = uf.find(wea[i].u as DefIntS);
+        let v = uf.find(wea[i].v as DefIntS);
+        if u != v {
+            println!("msf_check: edge {i} connects two trees of the forest");
+            return false;
+        }
+    }
+
+    let expected = kruskal_weight(wea);
+    let tolerance = 1e-6 * expected.abs().max(1.0);
+    if (weight - expected).abs() > tolerance {
+        println!("msf_check: forest weight {weight}, expected {expected}");
+        return false;
+    }
+    true
+}
+
+fn main() {
+    let args = Args::parse();
+    let wea = read_wgh_edge_array_from_file(&args.ifname);
+    let r: Vec<usize> = read_file_to_vec_seq(&args.rfname);
+    if check(&wea, &r) { println!("OK"); }
+    else { println!("ERR"); std::process::exit(1); }
+}
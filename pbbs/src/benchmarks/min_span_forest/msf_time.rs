@@ -40,30 +40,49 @@ use misc::*;
 use graph::WghEdgeArray;
 use io::write_slice_to_file_seq;
 use graph_io::read_wgh_edge_array_from_file;
-use msf::{ incremental_msf, serial_msf, inc_msf_mod };
+use msf::{
+    incremental_msf,
+    serial_msf,
+    inc_msf_mod,
+    boruvka_msf,
+    filter_kruskal_msf
+};
+use parlay::spec_for::SpecForStats;
 
-define_args!(Algs::INCREMENTAL);
+define_args!(
+    Algs::INCREMENTAL,
+    (stats, bool, false),
+    (stats_file, String, String::new())
+);
 
 define_algs!(
     (SERIAL, "serial"),
     (INCREMENTAL, "incremental"),
-    (INCMOD, "incmod")
+    (INCMOD, "incmod"),
+    (BORUVKA, "boruvka"),
+    (FILTERKRUSKAL, "filterkruskal")
 );
 
 
 pub fn run(
     alg: Algs,
     rounds: usize,
-    ea: WghEdgeArray
-) -> (Vec<DefInt>, Duration)
+    ea: WghEdgeArray,
+    stats: bool
+) -> (Vec<DefInt>, Duration, Option<SpecForStats>)
 {
     let sf = match alg {
         Algs::SERIAL => { serial_msf::minimum_spanning_forest },
         Algs::INCREMENTAL => { incremental_msf::minimum_spanning_forest },
         Algs::INCMOD => { inc_msf_mod::minimum_spanning_forest },
+        Algs::BORUVKA => { boruvka_msf::minimum_spanning_forest },
+        Algs::FILTERKRUSKAL => {
+            filter_kruskal_msf::minimum_spanning_forest
+        },
     };
 
     let mut r = vec![];
+    let mut st = None;
     let mut ea_copy = ea.clone();
     let ea_copy_shadow = unsafe {
         (&ea_copy as *const WghEdgeArray).as_ref().unwrap()
@@ -73,22 +92,33 @@ pub fn run(
         rounds,
         Duration::new(1, 0),
         || { if alg == Algs::INCMOD { ea_copy = ea.clone(); }},
-        || { sf(&ea_copy_shadow, &mut r); },
+        || {
+            st = match (stats, alg) {
+                (true, Algs::INCREMENTAL) => Some(incremental_msf::
+                    minimum_spanning_forest_stats(ea_copy_shadow, &mut r, true)),
+                (true, Algs::INCMOD) => Some(inc_msf_mod::
+                    minimum_spanning_forest_stats(ea_copy_shadow, &mut r, true)),
+                _ => { sf(ea_copy_shadow, &mut r); None }
+            };
+        },
         || {}
     );
-    (r, mean)
+    (r, mean, st)
 }
 
 fn main() {
     init!();
-    let args = Args::parse();
+    let mut args = Args::parse();
+    check_stats!(args, Algs::INCREMENTAL, Algs::INCMOD);
     let ea = read_wgh_edge_array_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, ea);
+    let (r, d, st) = run(args.algorithm, args.rounds, ea, args.stats);
 
+    let stats_file = args.stats_file.clone();
     finalize!(
         args,
         r,
         d,
         write_slice_to_file_seq(&r, args.ofname)
     );
+    print_stats!(stats_file, st);
 }
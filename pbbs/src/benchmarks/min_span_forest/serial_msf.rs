@@ -63,13 +63,9 @@ fn union_find_loop(es: &[IndexedEdge], m: usize, uf: &mut UnionFind, msf: &mut V
 }
 
 pub fn minimum_spanning_forest(wea: &WghEdgeArray, dest: &mut Vec<DefInt>) {
-    eprintln!(
-        "Serial MSF has an unidentified bug on some inputs. \
-        It's better not to use this function."
-    );
-
     let m = wea.m;
     let n = wea.n;
+    dest.clear();
 
     let mut wea: Vec<IndexedEdge> = (0..m)
         .map(|i| IndexedEdge::new(wea[i], i as u32))
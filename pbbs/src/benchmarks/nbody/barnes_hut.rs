@@ -0,0 +1,237 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+use enhanced_rayon::prelude::*;
+
+use parlay::{Timer, maybe_uninit_vec};
+use parlay::internal::sample_sort_inplace;
+use parlay::internal::binary_search::binary_search;
+use crate::common::geometry::{Point3d, Vector3d};
+
+type P = Point3d<f64>;
+type V = Vector3d<f64>;
+
+const LEAF_SIZE: usize = 16;
+const LEVELS: usize = 21;
+const SEQ_BUILD_THR: usize = 1 << 12;
+
+
+#[derive(Clone, Copy)]
+struct Particle {
+    pt: P,
+    code: u64,
+    idx: u32,
+}
+
+// *************************************************************
+//   OCTREE
+// *************************************************************
+
+/// A cell of the octree. Every cell keeps the total mass and the center of
+/// mass of the particles below it; leaves keep the range of their particles
+/// in the sorted particle array.
+struct Node {
+    size: f64,
+    com: P,
+    mass: f64,
+    start: usize,
+    end: usize,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool { self.children.is_empty() }
+
+    fn leaf(parts: &[Particle], size: f64, start: usize) -> Self {
+        let sum = parts.iter().fold(V::default(), |s, p| s + p.pt);
+        let mass = parts.len() as f64;
+        Self {
+            size,
+            com: sum / mass,
+            mass,
+            start,
+            end: start + parts.len(),
+            children: vec![],
+        }
+    }
+
+    fn internal(children: Vec<Node>, size: f64, start: usize) -> Self {
+        let (sum, mass) = children
+            .iter()
+            .fold((V::default(), 0.0), |(s, m), c| {
+                (s + c.com * c.mass, m + c.mass)
+            });
+        let end = children.last().unwrap().end;
+        Self { size, com: sum / mass, mass, start, end, children }
+    }
+}
+
+/// Interleaves the lowest `LEVELS` bits of x, y and z into a morton code.
+#[inline(always)]
+fn morton_code(x: u64, y: u64, z: u64) -> u64 {
+    let spread = |mut v: u64| {
+        v &= (1 << LEVELS) - 1;
+        v = (v | v << 32) & 0x1f00000000ffff;
+        v = (v | v << 16) & 0x1f0000ff0000ff;
+        v = (v | v << 8) & 0x100f00f00f00f00f;
+        v = (v | v << 4) & 0x10c30c30c30c30c3;
+        (v | v << 2) & 0x1249249249249249
+    };
+    spread(x) | spread(y) << 1 | spread(z) << 2
+}
+
+/// Builds the octree over `parts`, which must be sorted by their morton
+/// codes; the particles of every cell are then contiguous and the octants of
+/// a cell at `level` are found by a binary search on the next three bits.
+fn build(parts: &[Particle], size: f64, start: usize, level: usize) -> Node {
+    let n = parts.len();
+    if n <= LEAF_SIZE || level == LEVELS {
+        return Node::leaf(parts, size, start);
+    }
+
+    let shift = 3 * (LEVELS - level - 1);
+    let less = |a: Particle, b: Particle| {
+        (a.code >> shift) & 7 < (b.code >> shift) & 7
+    };
+    let probe = |o: u64| Particle { pt: P::default(), code: o << shift, idx: 0 };
+    let mut offsets = [n; 9];
+    offsets[..8]
+        .iter_mut()
+        .enumerate()
+        .for_each(|(o, off)| *off = binary_search(parts, probe(o as u64), less));
+
+    let build_child = |o: usize| {
+        let (s, e) = (offsets[o], offsets[o + 1]);
+        if s == e { None }
+        else { Some(build(&parts[s..e], size / 2.0, start + s, level + 1)) }
+    };
+    let children: Vec<Node> = if n < SEQ_BUILD_THR {
+        (0..8).filter_map(build_child).collect()
+    } else {
+        (0..8).into_par_iter().filter_map(build_child).collect()
+    };
+
+    Node::internal(children, size, start)
+}
+
+// *************************************************************
+//   FORCE CALCULATION
+// *************************************************************
+
+/// The force that a body of mass `mass` at `q` applies on a unit mass at `p`.
+#[inline(always)]
+fn force_from(p: P, q: P, mass: f64) -> V {
+    let d = q - p;
+    let r2 = d.dot(d);
+    if r2 == 0.0 { V::default() }
+    else { d * (mass / (r2 * r2.sqrt())) }
+}
+
+/// Barnes-Hut traversal: a cell is approximated by its center of mass when it
+/// is far enough from `p`, i.e. when size / distance < `alpha`.
+fn force_on(node: &Node, parts: &[Particle], p: Particle, alpha: f64) -> V {
+    if node.is_leaf() {
+        parts[node.start..node.end]
+            .iter()
+            .filter(|q| q.idx != p.idx)
+            .fold(V::default(), |f, q| f + force_from(p.pt, q.pt, 1.0))
+    } else {
+        let d = node.com - p.pt;
+        if node.size * node.size < alpha * alpha * d.dot(d) {
+            force_from(p.pt, node.com, node.mass)
+        } else {
+            node.children
+                .iter()
+                .fold(V::default(), |f, c| f + force_on(c, parts, p, alpha))
+        }
+    }
+}
+
+fn bounding_box(pts: &[P]) -> (P, P) {
+    let inf = f64::INFINITY;
+    pts
+        .par_iter()
+        .fold_with(
+            (P::new(inf, inf, inf), P::new(-inf, -inf, -inf)),
+            |(l, h), p| (
+                P::new(l.x.min(p.x), l.y.min(p.y), l.z.min(p.z)),
+                P::new(h.x.max(p.x), h.y.max(p.y), h.z.max(p.z))
+            )
+        )
+        .reduce(
+            || (P::new(inf, inf, inf), P::new(-inf, -inf, -inf)),
+            |(l1, h1), (l2, h2)| (
+                P::new(l1.x.min(l2.x), l1.y.min(l2.y), l1.z.min(l2.z)),
+                P::new(h1.x.max(h2.x), h1.y.max(h2.y), h1.z.max(h2.z))
+            )
+        )
+}
+
+pub fn forces(pts: &[P], alpha: f64, dest: &mut Vec<V>) {
+    let mut t = Timer::new("nbody"); //t.start();
+    let n = pts.len();
+    if n == 0 { *dest = vec![]; return; }
+
+    // the root cell is the smallest cube containing all the particles
+    let (lo, hi) = bounding_box(pts);
+    let d = hi - lo;
+    let size = d.x.max(d.y).max(d.z) * (1.0 + 1e-9) + f64::MIN_POSITIVE;
+    t.next("bounding box");
+
+    // sort the particles along the morton curve of the root cell
+    let scale = (1u64 << LEVELS) as f64 / size;
+    let max_coord = (1u64 << LEVELS) - 1;
+    let coord = |v: f64| ((v * scale) as u64).min(max_coord);
+    let mut parts: Vec<Particle> = pts
+        .par_iter()
+        .enumerate()
+        .map(|(i, &pt)| {
+            let q = pt - lo;
+            let code = morton_code(coord(q.x), coord(q.y), coord(q.z));
+            Particle { pt, code, idx: i as u32 }
+        }).collect();
+    sample_sort_inplace(&mut parts, |a, b| a.code < b.code, false);
+    t.next("morton sort");
+
+    let root = build(&parts, size, 0, 0);
+    t.next("build tree");
+
+    // calculate the forces in the tree order for locality
+    let fs: Vec<V> = parts
+        .par_iter()
+        .map(|&p| force_on(&root, &parts, p, alpha))
+        .collect();
+    t.next("forces");
+
+    let idxs: Vec<u32> = parts.par_iter().map(|p| p.idx).collect();
+    *dest = maybe_uninit_vec![V::default(); n];
+    dest
+        .par_ind_iter_mut(&idxs)
+        .zip(fs.par_iter())
+        .for_each(|(d, f)| *d = *f);
+    t.next("write back");
+}
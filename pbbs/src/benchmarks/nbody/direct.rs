@@ -0,0 +1,52 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use crate::common::geometry::{Point3d, Vector3d};
+
+type P = Point3d<f64>;
+type V = Vector3d<f64>;
+
+
+/// Exact O(n^2) summation of the forces on every particle (unit masses).
+pub fn forces(pts: &[P], _alpha: f64, dest: &mut Vec<V>) {
+    *dest = pts
+        .par_iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            pts
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(V::default(), |f, (_, &q)| {
+                    let d = q - p;
+                    let r2 = d.dot(d);
+                    if r2 == 0.0 { f }
+                    else { f + d * (1.0 / (r2 * r2.sqrt())) }
+                })
+        }).collect();
+}
@@ -0,0 +1,119 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+use clap::Parser;
+use rayon::prelude::*;
+
+#[path ="../../misc.rs"] mod misc;
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../common/geometry.rs"] mod geometry;
+#[path ="../../common/geometry_io.rs"] mod geometry_io;
+
+// only the geometry modules, where they expect them
+mod common { pub(crate) use super::{io, geometry, geometry_io}; }
+
+use parlay::utilities::hash64;
+use common::geometry::{Point3d, Vector3d};
+use common::geometry_io::read_points3d_from_file;
+
+type P = Point3d<f64>;
+type V = Vector3d<f64>;
+
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// nbody results filename
+    #[clap(value_parser, required=true)]
+    rfname: String,
+
+    /// the input points' filename
+    #[clap(value_parser, required=true)]
+    ifname: String,
+
+    /// the number of sampled particles
+    #[clap(short, long, value_parser, required=false, default_value_t=200)]
+    samples: usize,
+
+    /// the maximum accepted root mean square of the relative errors
+    #[clap(short, long, value_parser, required=false, default_value_t=1e-3)]
+    tolerance: f64,
+}
+
+fn direct_force(pts: &[P], i: usize) -> V {
+    let p = pts[i];
+    pts
+        .par_iter()
+        .enumerate()
+        .filter(|&(j, _)| j != i)
+        .map(|(_, &q)| {
+            let d = q - p;
+            let r2 = d.dot(d);
+            if r2 == 0.0 { V::default() }
+            else { d * (1.0 / (r2 * r2.sqrt())) }
+        })
+        .reduce(V::default, |a, b| a + b)
+}
+
+pub fn check(pts: &[P], forces: &[V], samples: usize, tolerance: f64) -> bool {
+    let n = pts.len();
+    if forces.len() != n {
+        eprintln!("expected {} forces but found {}.", n, forces.len());
+        return false;
+    }
+    if n == 0 { return true; }
+
+    let samples = samples.min(n);
+    let mut err_sum = 0.0f64;
+    let mut err_max = 0.0f64;
+    for s in 0..samples {
+        let i = if samples == n { s } else { hash64(s as u64) as usize % n };
+        let exact = direct_force(pts, i);
+        let diff = (forces[i] - exact).length();
+        let len = exact.length();
+        let err = if len > 0.0 { diff / len } else { diff };
+        err_sum += err * err;
+        err_max = err_max.max(err);
+    }
+    let rms = (err_sum / samples as f64).sqrt();
+
+    if rms.is_nan() || rms > tolerance {
+        eprintln!(
+            "relative error too large: rms={:e} max={:e} (tolerance={:e})",
+            rms, err_max, tolerance
+        );
+        false
+    } else { true }
+}
+
+fn main() {
+    let args = Args::parse();
+    let pts = read_points3d_from_file(&args.ifname);
+    let forces = read_points3d_from_file(&args.rfname);
+    if check(&pts, &forces, args.samples, args.tolerance) { println!("OK"); }
+    else { eprintln!("ERR"); std::process::exit(1); }
+}
@@ -0,0 +1,93 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+#[path ="mod.rs"] mod nbody;
+#[path ="../../misc.rs"] mod misc;
+#[path ="../macros.rs"] mod macros;
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../common/geometry.rs"] mod geometry;
+#[path ="../../common/geometry_io.rs"] mod geometry_io;
+
+// only the geometry modules, where they expect them
+mod common { pub(crate) use super::{io, geometry, geometry_io}; }
+
+use nbody::{barnes_hut, direct};
+use common::geometry::{Point3d, Vector3d};
+use common::geometry_io::{read_points3d_from_file, write_points3d_to_file};
+
+type P = Point3d<f64>;
+type V = Vector3d<f64>;
+
+define_args!(
+    Algs::BARNESHUT,
+    (alpha, f64, 0.3)
+);
+
+define_algs!(
+    (BARNESHUT, "barneshut"),
+    (DIRECT, "direct")
+);
+
+pub fn run(
+    alg: Algs,
+    rounds: usize,
+    pts: &[P],
+    alpha: f64
+) -> (Vec<V>, Duration) {
+    let f = match alg {
+        Algs::BARNESHUT => barnes_hut::forces,
+        Algs::DIRECT => direct::forces,
+    };
+
+    let mut r = vec![];
+    let mean = time_loop(
+        "nbody",
+        rounds,
+        Duration::new(1, 0),
+        || {},
+        || { f(pts, alpha, &mut r); },
+        || {}
+    );
+    (r, mean)
+}
+
+fn main() {
+    init!();
+    let args = Args::parse();
+    let pts = read_points3d_from_file(&args.ifname);
+    let (r, d) = run(args.algorithm, args.rounds, &pts, args.alpha);
+
+    finalize!(
+        args,
+        r,
+        d,
+        write_points3d_to_file(&r, args.ofname)
+    );
+}
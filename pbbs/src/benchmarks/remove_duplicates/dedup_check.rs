@@ -43,10 +43,14 @@ struct Args {
     ifname: String,
 }
 
-pub fn check(inp: &mut [i64], out: &mut [i64]) -> bool {
+pub fn check<T: Ord + Send>(inp: &mut [T], out: &mut [T]) -> bool {
     inp.par_sort_unstable();
     let (inp_dedup, _) = inp.partition_dedup();
     out.par_sort_unstable();
+    if out.len() != inp_dedup.len() {
+        eprintln!("output has {} elements, expected {}.", out.len(), inp_dedup.len());
+        return false;
+    }
 
     let mut diff_count = 0usize;
     for i in 0..inp_dedup.len() {
@@ -63,7 +67,20 @@ pub fn check(inp: &mut [i64], out: &mut [i64]) -> bool {
 
 fn main() {
     let args = Args::parse();
-    let mut inp = read_file_to_vec(&args.ifname, Some(|a: &[&str]| assert_eq!(a[0], "sequenceInt")));
+    let inp_s = std::fs::read_to_string(&args.ifname)
+        .expect("cannot read input file");
+    if inp_s.starts_with("sequenceChar") {
+        let out_s = std::fs::read_to_string(&args.rfname)
+            .expect("cannot read result file");
+        let mut inp: Vec<_> = inp_s.split_ascii_whitespace().skip(1).collect();
+        let mut out: Vec<_> = out_s.split_ascii_whitespace().collect();
+        if check(&mut inp, &mut out) { println!("OK"); }
+        else { eprintln!("ERR"); std::process::exit(1); }
+        return;
+    }
+    drop(inp_s);
+
+    let mut inp: Vec<i64> = read_file_to_vec(&args.ifname, Some(|a: &[&str]| assert_eq!(a[0], "sequenceInt")));
     let mut out = read_file_to_vec(&args.rfname, Some(|_: &[&str]| {}));
     if check(&mut inp, &mut out) { println!("OK"); }
     else { eprintln!("ERR"); std::process::exit(1); }
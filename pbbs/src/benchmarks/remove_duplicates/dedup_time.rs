@@ -24,6 +24,8 @@
 // SOFTWARE.
 // ============================================================================
 
+use std::fs;
+use std::hash::Hash;
 use std::time::Duration;
 
 #[path ="mod.rs"] mod dedup;
@@ -32,6 +34,7 @@ use std::time::Duration;
 #[path ="../../common/io.rs"] mod io;
 
 use dedup::parlay_hash;
+use parlay::primitives::tokens;
 use io::{read_big_file_to_vec, write_slice_to_file_seq};
 
 define_args!(Algs::PARHASH);
@@ -49,20 +52,65 @@ pub fn run(alg: Algs, rounds: usize, arr: &[u32]) -> (Vec<u32>, Duration) {
         "dedup",
         rounds,
         Duration::new(1, 0),
-        || { unsafe { *(r_ptr as *mut Vec<u32>) = vec![]; } },
+        || { unsafe { *(r_ptr as *mut Vec<u32>).as_mut().unwrap() = vec![]; } },
         || { f(&arr, &mut r); },
         || {}
     );
     (r, mean)
 }
 
+pub fn run_hashed<T>(alg: Algs, rounds: usize, arr: &[T]) -> (Vec<T>, Duration)
+where
+    T: Hash + Eq + Clone + Send + Sync,
+{
+    let f = match alg {
+        Algs::PARHASH => {parlay_hash::dedup_hashed},
+    };
+
+    let mut r = vec![];
+    let r_ptr = &r as *const Vec<T> as usize;
+
+    let mean = time_loop(
+        "dedup",
+        rounds,
+        Duration::new(1, 0),
+        || { unsafe { *(r_ptr as *mut Vec<T>).as_mut().unwrap() = vec![]; } },
+        || { f(arr, &mut r); },
+        || {}
+    );
+    (r, mean)
+}
+
 fn main() {
     init!();
     let args = Args::parse();
+
+    let s = fs::read(&args.ifname).expect("cannot read input file");
+    if s.starts_with(b"sequenceChar") {
+        // the strings are the whitespace separated words after the header
+        let words = tokens(&s, |c: u8| c.is_ascii_whitespace());
+        let (r, d) = run_hashed(args.algorithm, args.rounds, &words[1..]);
+
+        finalize!(
+            args,
+            r,
+            d,
+            {
+                let r: Vec<_> = r
+                    .par_iter()
+                    .map(|w| String::from_utf8_lossy(w))
+                    .collect();
+                write_slice_to_file_seq(&r, args.ofname)
+            }
+        );
+        return;
+    }
+    drop(s);
+
     let mut arr = Vec::new();
     read_big_file_to_vec(
         &args.ifname,
-        Some { 0: |w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")} },
+        Some(|w: &[&str]| {debug_assert_eq!(w[0], "sequenceInt")}),
         &mut arr
     );
     let (r, d) = run(args.algorithm, args.rounds, &arr);
@@ -25,11 +25,17 @@
 // ============================================================================
 
 use num_traits::PrimInt;
+use std::hash::Hash;
 
-use parlay::internal::group_by::remove_duplicates;
+use parlay::internal::group_by::{remove_duplicates, remove_duplicates_hashed};
 
 
 pub fn dedup<T: PrimInt + Default + Send + Sync>(inp: &[T], res: &mut Vec<T>)
 {
     remove_duplicates(inp, res);
 }
+
+pub fn dedup_hashed<T: Hash + Eq + Clone + Send + Sync>(inp: &[T], res: &mut Vec<T>)
+{
+    remove_duplicates_hashed(inp, res);
+}
@@ -30,8 +30,7 @@ use rayon::prelude::*;
 use crate::{DefIntS, DefInt};
 use crate::graph::EdgeArray;
 
-#[path="../../common/spec_for.rs"] mod spec_for;
-use spec_for::{Reservation, StatefulSpecFor};
+use parlay::spec_for::{Reservation, SpecFor, SpecForStats};
 
 #[cfg(not(feature = "AW_safe"))]
 use crate::union_find::UnionFind;
@@ -46,6 +45,14 @@ struct SFState {
 }
 
 pub fn spanning_forest(ea: &EdgeArray) -> Vec<u32> {
+    spanning_forest_stats(ea, false).0
+}
+
+/// also returns the statistics of the speculative loop
+pub fn spanning_forest_stats(
+    ea: &EdgeArray,
+    detailed: bool
+) -> (Vec<u32>, SpecForStats) {
     let m = ea.non_zeros;
     let n = ea.num_rows;
 
@@ -95,18 +102,17 @@ pub fn spanning_forest(ea: &EdgeArray) -> Vec<u32> {
         } else { false }
     };
 
-    let (rcs, ccs) = (Some(1024), Some(4096));
-    (0..m).stateful_spec_for(
-        reserve,
-        commit,
-        SFState { u: -1, v: -1 },
-        100,
-        rcs,
-        ccs
-    ).expect("failed speculative for");
-
-    rs
+    let stats = SpecFor::new()
+        .granularity(100)
+        .reserve_chunk(1024)
+        .commit_chunk(4096)
+        .detailed_stats(detailed)
+        .run_stateful(&(0..m), reserve, commit, SFState { u: -1, v: -1 })
+        .expect("failed speculative for");
+
+    let sf = rs
         .into_par_iter()
         .filter_map(|r| if r.reserved() {Some(r.get())} else {None})
-        .collect()
+        .collect();
+    (sf, stats)
 }
@@ -26,3 +26,4 @@
 
 pub mod serial_sf;
 pub mod incremental_sf;
+pub mod union_find_sf;
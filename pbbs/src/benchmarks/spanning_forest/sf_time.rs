@@ -37,47 +37,81 @@ use std::time::Duration;
 #[path ="../../algorithm/union_find.rs"] mod union_find;
 
 use misc::*;
-use sf::{ incremental_sf, serial_sf };
+use sf::{ incremental_sf, serial_sf, union_find_sf };
 use graph_io::read_edge_array_from_file;
 use graph::EdgeArray;
+use union_find::FindStrategy;
 use io::write_slice_to_file_seq;
+use parlay::spec_for::SpecForStats;
 
-define_args!(Algs::INCREMENTAL);
+define_args!(
+    Algs::INCREMENTAL,
+    (stats, bool, false),
+    (stats_file, String, String::new()),
+    (find, FindStrategy, FindStrategy::default())
+);
 
 define_algs!(
     (SERIAL, "serial"),
-    (INCREMENTAL, "incremental")
+    (INCREMENTAL, "incremental"),
+    (UNITE, "unite")
 );
 
-pub fn run(alg: Algs, rounds: usize, ea: EdgeArray) -> (Vec<DefInt>, Duration) {
-    let sf = match alg {
-        Algs::SERIAL => { serial_sf::spanning_forest },
-        Algs::INCREMENTAL => { incremental_sf::spanning_forest },
+type SF = Box<dyn Fn(&EdgeArray) -> Vec<DefInt>>;
+
+pub fn run(
+    alg: Algs,
+    rounds: usize,
+    ea: EdgeArray,
+    stats: bool,
+    find: FindStrategy
+) -> (Vec<DefInt>, Duration, Option<SpecForStats>) {
+    let sf: SF = match alg {
+        Algs::SERIAL => Box::new(serial_sf::spanning_forest),
+        Algs::INCREMENTAL => Box::new(incremental_sf::spanning_forest),
+        Algs::UNITE => Box::new(move |ea: &EdgeArray|
+            union_find_sf::spanning_forest(ea, find)),
     };
 
     let mut r = vec![];
+    let mut st = None;
 
     let mean = time_loop(
         "sf",
         rounds,
         Duration::new(1, 0),
         || {},
-        || { r = sf(&ea); },
+        || {
+            if stats && alg == Algs::INCREMENTAL {
+                let (f, s) = incremental_sf::spanning_forest_stats(&ea, true);
+                r = f;
+                st = Some(s);
+            } else { r = sf(&ea); }
+        },
         || {}
     );
-    (r, mean)
+    (r, mean, st)
 }
 
 fn main() {
     init!();
-    let args = Args::parse();
+    let mut args = Args::parse();
+    check_stats!(args, Algs::INCREMENTAL);
     let g = read_edge_array_from_file(&args.ifname);
-    let (r, d) = run(args.algorithm, args.rounds, g);
+    let (r, d, st) = run(
+        args.algorithm,
+        args.rounds,
+        g,
+        args.stats,
+        args.find
+    );
 
+    let stats_file = args.stats_file.clone();
     finalize!(
         args,
         r,
         d,
         write_slice_to_file_seq(&r, args.ofname)
     );
+    print_stats!(stats_file, st);
 }
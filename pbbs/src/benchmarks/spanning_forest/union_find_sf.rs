@@ -0,0 +1,50 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use parlay::primitives::pack_index;
+use crate::{DefInt, DefIntS};
+use crate::graph::EdgeArray;
+use crate::union_find::{AtomicUnionFind, FindStrategy};
+
+// All the edges try to unite their endpoints at once and the ones that
+// succeed form the forest. Unlike incremental_sf, which forest is found
+// depends on the schedule.
+pub fn spanning_forest(ea: &EdgeArray, strategy: FindStrategy) -> Vec<DefInt> {
+    let n = ea.num_rows;
+    let m = ea.non_zeros;
+    let uf = AtomicUnionFind::with_strategy(n, strategy);
+
+    let flags: Vec<bool> = (0..m)
+        .into_par_iter()
+        .map(|i| uf.unite(ea[i].u as DefIntS, ea[i].v as DefIntS))
+        .collect();
+
+    let mut st = vec![];
+    pack_index(&flags, &mut st);
+    st
+}
@@ -0,0 +1,161 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+use enhanced_rayon::prelude::*;
+
+use parlay::internal::integer_sort::integer_sort;
+use parlay::internal::merge::merge;
+use parlay::internal::sequence_ops::scan_inplace;
+use crate::DefInt;
+use crate::suffix_array::{Symbol, reduce_alphabet};
+
+/// strings up to this length are sorted by comparing their suffixes
+const BASE_CASE_SIZE: usize = 64;
+
+
+/// the number of bits to store the values in `0..=k`
+fn bits(k: DefInt) -> usize { (DefInt::BITS - k.leading_zeros()) as usize }
+
+// The suffix array of `s`, whose symbols are in `1..=k`, by the skew
+// algorithm of Kärkkäinen and Sanders: the suffixes at positions 1 and 2
+// mod 3 are sorted recursively on a string of 2n/3 names of symbol triples,
+// the ones at 0 mod 3 by a radix sort using those ranks, and the two are
+// merged. Every round is O(n) work, also on repetitive strings.
+fn dc3(s: &[DefInt], k: DefInt) -> Vec<DefInt> {
+    let n = s.len();
+    if n <= BASE_CASE_SIZE {
+        let mut sa: Vec<DefInt> = (0..n as DefInt).collect();
+        sa.sort_unstable_by(|&a, &b| s[a as usize..].cmp(&s[b as usize..]));
+        return sa;
+    }
+
+    let get = |i: usize| if i < n { s[i] } else { 0 };
+    let (n0, n1, n2) = (n.div_ceil(3), (n + 1) / 3, n / 3);
+    let n02 = n0 + n2;
+
+    // the positions 1 and 2 mod 3, with a dummy one at n if n % 3 == 1 so
+    // that the reduced string always ends with the 1 mod 3 half
+    let s12: Vec<DefInt> = (0..n + n0 - n1)
+        .into_par_iter()
+        .filter(|i| i % 3 != 0)
+        .map(|i| i as DefInt)
+        .collect();
+
+    // sort them by their first three symbols and name the distinct triples
+    let b = bits(k);
+    let triple = |i: DefInt| -> u128 {
+        let i = i as usize;
+        (get(i) as u128) << (2 * b) | (get(i + 1) as u128) << b
+            | get(i + 2) as u128
+    };
+    let mut sa12 = vec![];
+    integer_sort(&s12, &triple, 3 * b, &mut sa12);
+
+    let mut names: Vec<DefInt> = (0..n02)
+        .into_par_iter()
+        .map(|i| {
+            (i == 0 || triple(sa12[i]) != triple(sa12[i - 1])) as DefInt
+        }).collect();
+    let num_names = scan_inplace(&mut names, true, |a, b| a + b);
+
+    // the reduced string: the names at 1 mod 3, then the ones at 2 mod 3
+    let reduced = |p: usize| if p % 3 == 1 { p / 3 } else { p / 3 + n0 };
+    let mut r12 = vec![0; n02];
+    r12
+        .par_ind_iter_mut_by(|i| reduced(sa12[i] as usize), n02)
+        .zip(names.par_iter())
+        .for_each(|(r, &name)| *r = name);
+
+    // rank the sample suffixes, recursing unless all names are distinct
+    let sa12 = if (num_names as usize) < n02 {
+        let sa = dc3(&r12, num_names);
+        r12
+            .par_ind_iter_mut(&sa)
+            .enumerate()
+            .for_each(|(i, r)| *r = i as DefInt + 1);
+        sa
+    } else {
+        let mut sa = vec![0; n02];
+        sa
+            .par_ind_iter_mut_by(|i| r12[i] as usize - 1, n02)
+            .enumerate()
+            .for_each(|(i, x)| *x = i as DefInt);
+        sa
+    };
+    let rank = |p: usize| if p < n { r12[reduced(p)] } else { 0 };
+
+    // the sample positions in order, without the dummy
+    let sa12: Vec<DefInt> = sa12
+        .into_par_iter()
+        .map(|x| {
+            let x = x as usize;
+            (if x < n0 { 3 * x + 1 } else { 3 * (x - n0) + 2 }) as DefInt
+        })
+        .filter(|&p| (p as usize) < n)
+        .collect();
+
+    // the positions 0 mod 3, by their symbol and the rank of the next suffix
+    let s0: Vec<DefInt> = (0..n0)
+        .into_par_iter()
+        .map(|i| 3 * i as DefInt)
+        .collect();
+    let key0 = |p: DefInt| -> u64 {
+        (s[p as usize] as u64) << DefInt::BITS | rank(p as usize + 1) as u64
+    };
+    let mut sa0 = vec![];
+    integer_sort(&s0, &key0, b + DefInt::BITS as usize, &mut sa0);
+
+    // two suffixes compare by their first d symbols and the ranks of the
+    // suffixes after them, for the first d that makes both of those samples
+    let less = |i: DefInt, j: DefInt| {
+        let (i, j) = (i as usize, j as usize);
+        let d = (0..3).find(|d| (i + d) % 3 != 0 && (j + d) % 3 != 0).unwrap();
+        for t in 0..d {
+            let (a, b) = (get(i + t), get(j + t));
+            if a != b { return a < b; }
+        }
+        rank(i + d) < rank(j + d)
+    };
+    let mut sa = vec![0; n];
+    merge(&sa0, &sa12, &mut sa, less);
+    sa
+}
+
+#[cfg(not(feature = "AW_safe"))]
+pub fn suffix_array<T: Symbol>(s: &[T], r: &mut [DefInt]) {
+    let (s, k) = reduce_alphabet(s);
+    r.copy_from_slice(&dc3(&s, k));
+}
+
+#[cfg(feature = "AW_safe")]
+pub fn suffix_array<T: Symbol>(s: &[T], r: &mut [crate::DefAtomInt]) {
+    let (s, k) = reduce_alphabet(s);
+    r
+        .par_iter_mut()
+        .zip(dc3(&s, k))
+        .for_each(|(r, x)| *r.get_mut() = x);
+}
@@ -25,3 +25,4 @@
 // ============================================================================
 
 pub(crate) mod parallel_range;
+pub(crate) mod dc3;
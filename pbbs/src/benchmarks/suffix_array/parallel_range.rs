@@ -24,16 +24,15 @@
 // SOFTWARE.
 // ============================================================================
 
-use crate::suffix_array;
-use crate::DefChar;
+use crate::suffix_array::{self, Symbol};
 
 
 #[cfg(not(feature = "AW_safe"))]
-pub fn suffix_array(s: &[DefChar], r: &mut [crate::DefInt]) {
+pub fn suffix_array<T: Symbol>(s: &[T], r: &mut [crate::DefInt]) {
     suffix_array::suffix_array(s, r);
 }
 
 #[cfg(feature = "AW_safe")]
-pub fn suffix_array(s: &[DefChar], r: &mut [crate::DefAtomInt]) {
+pub fn suffix_array<T: Symbol>(s: &[T], r: &mut [crate::DefAtomInt]) {
     suffix_array::atomic_suffix_array(s, r);
 }
@@ -0,0 +1,152 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+use clap::Parser;
+
+#[path ="../../misc.rs"] mod misc;
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../algorithm/range_min.rs"] mod range_min;
+
+use misc::*;
+use range_min::RangeMin;
+use io::{chars_from_file, ints_from_chars, read_file_to_vec_seq};
+
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// suffix array results filename
+    #[clap(value_parser, required=true)]
+    rfname: String,
+
+    /// the input string's filename
+    #[clap(value_parser, required=true)]
+    ifname: String,
+
+    /// an LCP array to check as well (computed from the SA if not given)
+    #[clap(long, value_parser)]
+    lcp: Option<String>,
+}
+
+/// The LCPs of adjacent suffixes in `sa` by Kasai et al.'s algorithm. Unlike
+/// `lcp::lcp` it stays in bounds for any permutation `sa`, sorted or not.
+fn kasai_lcp<T: Ord>(s: &[T], sa: &[DefInt], isa: &[DefInt]) -> Vec<DefInt> {
+    let n = s.len();
+    let mut l = vec![0; n - 1];
+    let mut h = 0;
+    for i in 0..n {
+        let r = isa[i] as usize;
+        if r == 0 { h = 0; continue; }
+        let j = sa[r - 1] as usize;
+        while i + h < n && j + h < n && s[i + h] == s[j + h] { h += 1; }
+        l[r - 1] = h as DefInt;
+        h = h.saturating_sub(1);
+    }
+    l
+}
+
+/// Checks that `sa` sorts the suffixes of `s` and that `l` (or, if `None`, the
+/// LCPs computed from `sa`) holds the LCPs of its adjacent suffixes. Each pair
+/// is checked on its first mismatch and, via a range minimum over the LCPs,
+/// on the pair of suffixes one symbol shorter.
+pub fn check<T: Ord>(
+    s: &[T],
+    sa: &[DefInt],
+    l: Option<Vec<DefInt>>
+) -> bool {
+    let n = s.len();
+    if sa.len() != n {
+        println!("sa_check: {} suffixes, expected {n}", sa.len());
+        return false;
+    }
+    if n < 2 { return n == 0 || sa[0] == 0; }
+
+    let mut isa = vec![DefInt::MAX; n];
+    for (i, &p) in sa.iter().enumerate() {
+        if p as usize >= n || isa[p as usize] != DefInt::MAX {
+            println!("sa_check: invalid or repeated suffix {p}");
+            return false;
+        }
+        isa[p as usize] = i as DefInt;
+    }
+
+    let l = l.unwrap_or_else(|| kasai_lcp(s, sa, &isa));
+    if l.len() != n - 1 {
+        println!("sa_check: {} LCPs, expected {}", l.len(), n - 1);
+        return false;
+    }
+
+    let rq = RangeMin::new(&l, |a, b| a < b, 111);
+    for i in 0..n - 1 {
+        let (a, b) = (sa[i] as usize, sa[i + 1] as usize);
+        let li = l[i] as usize;
+        if a + li > n || b + li > n {
+            println!("sa_check: LCP {li} at {i} is too long");
+            return false;
+        }
+
+        // the suffixes differ right after their common prefix
+        if b + li == n || (a + li < n && s[a + li] >= s[b + li]) {
+            println!("sa_check: suffixes {a} and {b} at {i} are out of order");
+            return false;
+        }
+
+        // and agree on it: the first symbol, then the rest by induction
+        if li == 0 { continue; }
+        if s[a] != s[b] {
+            println!("sa_check: LCP {li} at {i} is too long");
+            return false;
+        }
+        let rest = if a + 1 == n { 0 } else {
+            let (ra, rb) = (isa[a + 1], isa[b + 1]);
+            if ra >= rb {
+                println!("sa_check: suffixes {a} and {b} at {i} are out of order");
+                return false;
+            }
+            l[rq.query(ra, rb - 1) as usize] as usize
+        };
+        if rest != li - 1 {
+            println!("sa_check: LCP {li} at {i}, expected {}", rest + 1);
+            return false;
+        }
+    }
+    true
+}
+
+fn main() {
+    let args = Args::parse();
+    let arr = chars_from_file(&args.ifname, false).unwrap();
+    let sa: Vec<DefInt> = read_file_to_vec_seq(&args.rfname);
+    let l = args.lcp.map(read_file_to_vec_seq);
+    let ok = match ints_from_chars(&arr) {
+        Ok(Some(ints)) => check(&ints, &sa, l),
+        Ok(None) => check(&arr, &sa, l),
+        Err(e) => { println!("sa_check: {e}"); false }
+    };
+    if ok { println!("OK"); }
+    else { println!("ERR"); std::process::exit(1); }
+}
@@ -33,22 +33,33 @@ use rayon::prelude::*;
 #[path ="../macros.rs"] mod macros;
 #[path ="../../common/io.rs"] mod io;
 #[path ="../../algorithm/suffix_array.rs"] mod suffix_array;
+#[path ="../../algorithm/range_min.rs"] mod range_min;
+#[path ="../../algorithm/lcp.rs"] mod lcp;
 
 use misc::*;
-use sa::parallel_range;
-use io::{chars_from_file, write_slice_to_file_seq};
+use sa::{ parallel_range, dc3 };
+use lcp::lcp;
+use suffix_array::Symbol;
+use io::{chars_from_file, ints_from_chars, write_slice_to_file_seq};
 
-define_args!(Algs::ParRange);
-define_algs!((ParRange, "par-range"));
+define_args!(
+    Algs::ParRange,
+    (lcp, String, String::new())
+);
+define_algs!(
+    (ParRange, "par-range"),
+    (DC3, "dc3")
+);
 
-pub fn run(
+pub fn run<T: Symbol>(
     alg: Algs,
     rounds: usize,
-    inp: &[DefChar]
+    inp: &[T]
 ) -> (Vec<DefInt>, Duration)
 {
     let f = match alg {
-        Algs::ParRange => {parallel_range::suffix_array},
+        Algs::ParRange => {parallel_range::suffix_array::<T>},
+        Algs::DC3 => {dc3::suffix_array::<T>},
     };
 
     #[cfg(not(feature = "AW_safe"))]
@@ -67,7 +78,7 @@ pub fn run(
         rounds,
         Duration::new(1, 0),
         || {},
-        || { f(&inp, &mut r); },
+        || { f(inp, &mut r); },
         || {}
     );
     #[cfg(feature = "AW_safe")]
@@ -79,7 +90,23 @@ fn main() {
     init!();
     let args = Args::parse();
     let arr = chars_from_file(&args.ifname, false).unwrap();
-    let (r, d) = run(args.algorithm, args.rounds, &arr);
+    let ints = ints_from_chars(&arr).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(1);
+    });
+    let (r, d) = match &ints {
+        Some(ints) => run(args.algorithm, args.rounds, ints),
+        None => run(args.algorithm, args.rounds, &arr),
+    };
+
+    // the LCPs of adjacent suffixes, if asked for
+    if !args.lcp.is_empty() {
+        let lcps = match &ints {
+            Some(ints) => lcp(ints, &r),
+            None => lcp(&arr, &r),
+        };
+        write_slice_to_file_seq(&lcps, &args.lcp);
+    }
 
     finalize!(
         args,
@@ -0,0 +1,55 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use parlay::Timer;
+use parlay::utilities::hash_bytes;
+use parlay::internal::sample_sort_inplace;
+use parlay::internal::group_by::histogram_by_key;
+use crate::{DefChar, DefInt};
+use crate::words::{normalize, words};
+
+
+pub fn word_counts(s: &[DefChar]) -> Vec<(String, DefInt)> {
+    let mut t = Timer::new("wc"); //t.start();
+
+    let normalized = normalize(s);
+    let words = words(&normalized);
+    t.next("tokens");
+
+    let mut counts: Vec<(&[DefChar], DefInt)> = vec![];
+    histogram_by_key(&words, |w: &[DefChar]| hash_bytes(w) as usize, &mut counts);
+    t.next("histogram");
+
+    sample_sort_inplace(&mut counts, |a, b| a.0 < b.0, false);
+    t.next("sort");
+
+    counts
+        .into_par_iter()
+        .map(|(w, c)| (String::from_utf8(w.to_vec()).unwrap(), c))
+        .collect()
+}
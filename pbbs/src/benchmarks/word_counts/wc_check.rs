@@ -0,0 +1,84 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::collections::HashMap;
+use clap::Parser;
+
+#[path ="../../common/io.rs"] mod io;
+#[path ="../../common/words.rs"] mod words;
+use io::{chars_from_file, read_file_to_vec_seq};
+use words::normalize;
+
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// wc results filename
+    #[clap(value_parser, required=true)]
+    rfname: String,
+
+    /// the input text's filename
+    #[clap(value_parser, required=true)]
+    ifname: String,
+}
+
+pub fn check(inp: &[u8], out: &[String]) -> bool {
+    let mut hist: HashMap<String, usize> = HashMap::new();
+    normalize(inp)
+        .split(|&c| c == b' ')
+        .filter(|w| !w.is_empty())
+        .for_each(|w| {
+            let w = String::from_utf8(w.to_vec()).unwrap();
+            *hist.entry(w).or_insert(0) += 1;
+        });
+    let mut hist: Vec<_> = hist.into_iter().collect();
+    hist.sort_unstable();
+
+    if hist.len() != out.len() {
+        eprintln!("expected {} words but found {}.", hist.len(), out.len());
+        return false;
+    }
+
+    let diff_count = hist
+        .iter()
+        .zip(out.iter())
+        .filter(|((w, c), o)| **o != format!("{w} {c}"))
+        .count();
+    if diff_count != 0 {
+        eprintln!("output file has {diff_count} differences.");
+        false
+    } else { true }
+}
+
+fn main() {
+    let args = Args::parse();
+    let inp = chars_from_file(&args.ifname, false).unwrap();
+    let out: Vec<String> = read_file_to_vec_seq(&args.rfname)
+        .into_iter()
+        .filter(|l: &String| !l.is_empty())
+        .collect();
+    if check(&inp, &out) { println!("OK"); }
+    else { eprintln!("ERR"); std::process::exit(1); }
+}
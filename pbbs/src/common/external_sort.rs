@@ -0,0 +1,259 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::mem::{size_of, size_of_val};
+use std::path::{Path, PathBuf};
+use std::slice::{from_raw_parts, from_raw_parts_mut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rayon::prelude::*;
+
+use parlay::maybe_uninit_vec;
+use parlay::internal::merge::kway_merge;
+use super::io::lines;
+
+
+/// the smallest read buffer of a run, in bytes. With many runs this takes
+/// precedence over the memory limit so that the merge does not degrade to
+/// reading a few elements at a time.
+const MIN_RUN_BUF: usize = 1 << 16;
+
+/// distinguishes the run files of the calls made by one process
+static CALL_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The directory for temporary files: `dir` if it is given, otherwise the
+/// system's (`$TMPDIR` if set).
+pub(crate) fn tmp_dir(dir: &str) -> PathBuf {
+    if dir.is_empty() { std::env::temp_dir() } else { PathBuf::from(dir) }
+}
+
+/// A sorted run spilled to a temporary file, read back one buffer at a time.
+struct Run<T> {
+    file: File,
+    path: PathBuf,
+    buf: Vec<T>,
+    pos: usize,
+    done: bool,
+}
+
+impl<T: Copy + Default> Run<T> {
+    fn spill(path: PathBuf, arr: &[T]) -> Self {
+        let bytes = unsafe {
+            from_raw_parts(arr.as_ptr() as *const u8, size_of_val(arr))
+        };
+        fs::write(&path, bytes).expect("cannot write run file");
+        let file = File::open(&path).expect("cannot open run file");
+        Self { file, path, buf: vec![], pos: 0, done: false }
+    }
+
+    fn remaining(&self) -> &[T] { &self.buf[self.pos..] }
+
+    /// reads up to `cap` more elements once the buffer is consumed
+    fn refill(&mut self, cap: usize) {
+        if self.done || self.pos < self.buf.len() { return; }
+        let mut buf = vec![T::default(); cap];
+        let bytes = unsafe {
+            from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, size_of::<T>() * cap)
+        };
+        let mut read = 0;
+        while read < bytes.len() {
+            match self.file.read(&mut bytes[read..]).expect("cannot read run") {
+                0 => break,
+                r => read += r,
+            }
+        }
+        buf.truncate(read / size_of::<T>());
+        self.done = buf.len() < cap;
+        self.buf = buf;
+        self.pos = 0;
+    }
+}
+
+impl<T> Drop for Run<T> {
+    fn drop(&mut self) { let _ = fs::remove_file(&self.path); }
+}
+
+/// The size of the blocks the input is read in: the text of a chunk, its
+/// values and the sort's scratch space share the memory budget.
+pub(crate) fn block_bytes(memory_limit: usize) -> usize {
+    (memory_limit / 4).max(1 << 12)
+}
+
+/// Calls `f` with the non-blank lines of consecutive blocks of about
+/// `block_bytes` bytes of the text file `ifname`, leaving out its header
+/// line. Stops at the first error `f` returns.
+pub(crate) fn for_each_block<F>(
+    ifname: &str,
+    block_bytes: usize,
+    mut f: F
+) -> Result<(), String> where
+    F: FnMut(Vec<&str>) -> Result<(), String>,
+{
+    let mut inp = File::open(ifname).expect("cannot read input file");
+    let mut carry: Vec<u8> = vec![];
+    let mut header = true;
+    loop {
+        let mut block = std::mem::take(&mut carry);
+        let s = block.len();
+        block.resize(s + block_bytes, 0);
+        let mut read = s;
+        while read < block.len() {
+            match inp.read(&mut block[read..]).expect("cannot read input file") {
+                0 => break,
+                r => read += r,
+            }
+        }
+        block.truncate(read);
+        let eof = read < s + block_bytes;
+        if !eof {
+            // keep the last partial line for the next block
+            let cut = block.iter().rposition(|&c| c == b'\n').map_or(0, |i| i + 1);
+            carry = block.split_off(cut);
+        }
+
+        let text = std::str::from_utf8(&block).expect("input is not utf-8");
+        let mut w = lines(text);
+        if header && !w.is_empty() {
+            w.remove(0);
+            header = false;
+        }
+        w.retain(|l| !l.trim().is_empty());
+        f(w)?;
+        if eof { return Ok(()); }
+    }
+}
+
+/// The first `n` values of a file written by `external_sort`, parsed back
+/// with `parse`.
+pub(crate) fn read_head<T, P>(fname: &str, n: usize, parse: P) -> Vec<T>
+where
+    P: Fn(&str) -> Option<T>,
+{
+    let f = File::open(fname).expect("cannot read output file");
+    BufReader::new(f)
+        .lines()
+        .map_while(Result::ok)
+        .take(n)
+        .filter_map(|l| parse(&l))
+        .collect()
+}
+
+/// Sorts the values of the text file `ifname` (one per line, after the
+/// header) into `ofname`, keeping roughly at most `memory_limit` bytes of
+/// data in memory. The input is read and sorted with `sort` in chunks that
+/// are spilled to `tmp_dir` as sorted runs, which are then merged with a
+/// parallel k-way merge. The result is stable if `sort` is. Fails on the
+/// first non-empty line that `parse` rejects.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn external_sort<T, P, S, F, W>(
+    ifname: &str,
+    ofname: &str,
+    memory_limit: usize,
+    tmp_dir: &Path,
+    parse: P,
+    sort: S,
+    less: F,
+    write: W
+) -> Result<(), String> where
+    T: Copy + Default + Send + Sync,
+    P: Fn(&str) -> Option<T> + Send + Sync,
+    S: Fn(&mut Vec<T>),
+    F: Fn(T, T) -> bool + Copy + Send + Sync,
+    W: Fn(&T) -> String + Send + Sync,
+{
+    let tag = format!(
+        "pbbs_ext_{}_{}",
+        std::process::id(),
+        CALL_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
+    // phase 1: sort memory-sized chunks into runs
+    let mut runs: Vec<Run<T>> = vec![];
+    for_each_block(ifname, block_bytes(memory_limit), |w| {
+        let mut chunk: Vec<T> = w
+            .into_par_iter()
+            .map(|l| parse(l).ok_or_else(|| format!("cannot parse line {l:?}")))
+            .collect::<Result<_, _>>()?;
+        if !chunk.is_empty() {
+            sort(&mut chunk);
+            let path = tmp_dir.join(format!("{tag}_{}", runs.len()));
+            runs.push(Run::spill(path, &chunk));
+        }
+        Ok(())
+    })?;
+
+    // phase 2: merge the runs. In each step everything up to the smallest
+    // of the buffers' last elements can be merged and written out
+    let mut out = BufWriter::new(
+        File::create(Path::new(ofname)).expect("cannot write to output")
+    );
+    let t_size = size_of::<T>().max(1);
+    let cap = (memory_limit / 2 / t_size / runs.len().max(1))
+        .max(MIN_RUN_BUF / t_size);
+    let mut first = true;
+    loop {
+        runs.iter_mut().for_each(|r| r.refill(cap));
+        runs.retain(|r| !r.remaining().is_empty());
+        if runs.is_empty() { break; }
+
+        let bound = runs
+            .iter()
+            .map(|r| *r.remaining().last().unwrap())
+            .reduce(|a, b| if less(b, a) { b } else { a })
+            .unwrap();
+        // for stability, the elements equal to the bound can only be taken
+        // up to the first run that may have more of them on disk
+        let last_eq = runs
+            .iter()
+            .position(|r| !less(bound, *r.remaining().last().unwrap()))
+            .unwrap();
+        let parts: Vec<&[T]> = runs
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let rem = r.remaining();
+                let m = if i <= last_eq {
+                    rem.partition_point(|&x| !less(bound, x))
+                } else {
+                    rem.partition_point(|&x| less(x, bound))
+                };
+                &rem[..m]
+            }).collect();
+        let n: usize = parts.iter().map(|p| p.len()).sum();
+        let mut merged = maybe_uninit_vec![T::default(); n];
+        kway_merge(&parts, &mut merged, less);
+        let lens: Vec<usize> = parts.iter().map(|p| p.len()).collect();
+        runs.iter_mut().zip(lens).for_each(|(r, l)| r.pos += l);
+
+        let text: Vec<String> = merged.par_iter().map(&write).collect();
+        if !first { out.write_all(b"\n").expect("cannot write to output"); }
+        out.write_all(text.join("\n").as_bytes()).expect("cannot write to output");
+        first = false;
+    }
+    out.flush().expect("cannot write to output");
+    Ok(())
+}
@@ -149,7 +149,7 @@ impl Debug for ParsePoint2dError {
 
 pub type Vector3d<T> = Point3d<T>;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct Point3d<T> {
     pub x: T,
     pub y: T,
@@ -174,6 +174,13 @@ impl<T: Float> Point3d<T> {
     pub fn dot(self, other: Self) -> T {
         return self.x * other.x + self.y * other.y + self.z * other.z;
     }
+
+    // Returns the euclidean length of the vector
+    pub fn length(self) -> T { self.dot(self).sqrt() }
+}
+
+impl<T: Float> Default for Point3d<T> {
+    fn default() -> Self { Self { x: T::zero(), y: T::zero(), z: T::zero() } }
 }
 
 impl<T: Float> Add<Vector3d<T>> for Point3d<T> {
@@ -200,6 +207,14 @@ impl<T: Float> Mul<T> for Vector3d<T> {
     }
 }
 
+impl<T: Float> Div<T> for Vector3d<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        Self {x: self.x / rhs, y: self.y / rhs, z: self.z / rhs}
+    }
+}
+
 impl<T: Float + FromStr> FromStr for Point3d<T>
 where
     <T as std::str::FromStr>::Err: Debug
@@ -36,6 +36,7 @@ use crate::common::geometry::*;
 
 
 const HEADER_TRI: &str = "pbbs_triangles";
+const HEADER_P3D: &str = "pbbs_sequencePoint3d";
 
 pub fn read_points2d_from_file<T>(fname: &str) -> Vec<Point2d<T>>
 where
@@ -55,10 +56,25 @@ where
 {
     read_file_to_vec(
         fname,
-        Some {0: |w: &[&str]| debug_assert_eq!(w[0], "pbbs_sequencePoint3d")}
+        Some {0: |w: &[&str]| debug_assert_eq!(w[0], HEADER_P3D)}
     )
 }
 
+pub fn write_points3d_to_file<T, F>(pnts: &[Point3d<T>], fname: F)
+where
+    T: Float + std::fmt::Display + Sync,
+    F: AsRef<std::path::Path>,
+{
+    let ps: Vec<_> = pnts
+        .par_iter()
+        .map(|p| p.to_string())
+        .collect();
+    fs::write(
+        fname,
+        format!("{}\n{}", HEADER_P3D, ps.join("\n"))
+    ).expect("cannot write to output");
+}
+
 pub fn read_triangles_from_file<P>(fname: &str, offset: usize) -> Triangles<P>
 where
     P: FromStr + Send,
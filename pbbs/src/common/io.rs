@@ -26,6 +26,7 @@
 
 use std::{fs, io, io::prelude::*};
 use rayon::prelude::*;
+use parlay::primitives::{split_at, tokens};
 
 #[allow(dead_code)]
 #[inline(always)]
@@ -77,6 +78,30 @@ where
         .collect::<Vec<T>>()
 }
 
+/// Splits the text `s` into its non-empty lines in parallel.
+#[allow(dead_code)]
+pub(crate) fn lines(s: &str) -> Vec<&str> {
+    tokens(s.as_bytes(), |c| c == b'\n')
+        .into_par_iter()
+        // splitting valid utf-8 at an ascii character keeps it valid
+        .map(|l| unsafe { std::str::from_utf8_unchecked(l) })
+        .collect()
+}
+
+/// Splits the text `s` at every newline in parallel, keeping the empty
+/// lines (the same pieces as `s.split('\n')`).
+#[allow(dead_code)]
+pub(crate) fn split_lines(s: &str) -> Vec<&str> {
+    let b = s.as_bytes();
+    let flags: Vec<bool> = b.par_iter().map(|&c| c == b'\n').collect();
+    split_at(b, &flags)
+        .into_par_iter()
+        .map(|l| l.strip_suffix(b"\n").unwrap_or(l))
+        // splitting valid utf-8 at an ascii character keeps it valid
+        .map(|l| unsafe { std::str::from_utf8_unchecked(l) })
+        .collect()
+}
+
 #[allow(dead_code)]
 pub(crate) fn read_file_to_vec<T, P, F>(
     fname: P,
@@ -89,7 +114,7 @@ pub(crate) fn read_file_to_vec<T, P, F>(
 {
     let s = fs::read_to_string(fname)
         .expect("cannot read input file");
-    let w: Vec<_> = s.par_split('\n').collect();
+    let w = split_lines(&s);
     if debug_assert.is_some() {
         debug_assert.unwrap()(&w);
     }
@@ -115,9 +140,10 @@ pub(crate) fn read_big_file_to_vec<T, P, F>(
     if debug_assert.is_some() {
         eprintln!("debug_assert is not supported for read_big_file_to_vec");
     }
-    *dest = fs::read_to_string(fname)
-        .expect("cannot read input file")
-        .par_split('\n')
+    let s = fs::read_to_string(fname)
+        .expect("cannot read input file");
+    *dest = split_lines(&s)
+        .into_par_iter()
         .map(str::parse)
         .filter(Result::is_ok)
         .map(Result::unwrap)
@@ -147,3 +173,70 @@ pub(crate) fn chars_to_file<P: AsRef<std::path::Path>>(
     f.write_all(buffer)?;
     Ok(())
 }
+
+/// The integers of a `sequenceInt` file read with `chars_from_file`,
+/// `Ok(None)` if it has any other header, or an error naming the first
+/// token that is not an integer.
+#[allow(dead_code)]
+pub(crate) fn ints_from_chars(arr: &[u8]) -> Result<Option<Vec<i64>>, String> {
+    if !arr.starts_with(b"sequenceInt") { return Ok(None); }
+    let text = std::str::from_utf8(arr).map_err(|e| e.to_string())?;
+    let ls = lines(text);
+    if ls[0].trim() != "sequenceInt" { return Ok(None); }
+    ls[1..]
+        .par_iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|l| l.parse().map_err(|_| format!("cannot parse integer {l:?}")))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// Reads the text file `fname`, checking that its first line is `header`.
+#[allow(dead_code)]
+pub(crate) fn read_with_header(fname: &str, header: &str) -> Result<String, String> {
+    let s = fs::read_to_string(fname).map_err(|e| format!("{fname}: {e}"))?;
+    let first = s.split('\n').next().unwrap_or("").trim();
+    if first != header {
+        return Err(format!("{fname}: expected a {header} file, found {first:?}"));
+    }
+    Ok(s)
+}
+
+/// Parses the token `t`, or returns an error naming it.
+#[allow(dead_code)]
+pub(crate) fn parse_token<T: std::str::FromStr>(t: &str) -> Result<T, String> {
+    t.parse().map_err(|_| format!("cannot parse {t:?}"))
+}
+
+/// The values of the lines `w`, one per line, or an error naming the first
+/// one that does not parse.
+#[allow(dead_code)]
+pub(crate) fn values_from_lines<T>(w: &[&str]) -> Result<Vec<T>, String>
+where
+    T: std::str::FromStr + Send,
+{
+    w
+        .par_iter()
+        .map(|l| parse_token(l.trim()))
+        .collect()
+}
+
+/// The "a b" pairs of the lines `w`, or an error naming the first line
+/// that is not a pair or token that does not parse.
+#[allow(dead_code)]
+pub(crate) fn pairs_from_lines<A, B>(w: &[&str]) -> Result<Vec<(A, B)>, String>
+where
+    A: std::str::FromStr + Send,
+    B: std::str::FromStr + Send,
+{
+    w
+        .par_iter()
+        .map(|l| {
+            let mut t = l.split_whitespace();
+            match (t.next(), t.next(), t.next()) {
+                (Some(a), Some(b), None) => Ok((parse_token(a)?, parse_token(b)?)),
+                _ => Err(format!("expected a pair, found {l:?}")),
+            }
+        }).collect()
+}
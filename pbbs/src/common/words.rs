@@ -0,0 +1,51 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+use rayon::prelude::*;
+
+use parlay::primitives::tokens;
+
+// A word is a maximal run of ASCII letters, compared case-insensitively.
+// wc, ii and their checkers all split the text through `fold`, so that they
+// agree on what the words are.
+
+
+/// The lower-case form of a letter, or a space for any other character.
+#[inline(always)]
+pub(crate) fn fold(c: u8) -> u8 {
+    if c.is_ascii_alphabetic() { c.to_ascii_lowercase() } else { b' ' }
+}
+
+/// The text `s` with its letters lower-cased and every other character
+/// replaced by a space.
+pub(crate) fn normalize(s: &[u8]) -> Vec<u8> {
+    s.par_iter().map(|&c| fold(c)).collect()
+}
+
+/// The words of a text returned by `normalize`, in order.
+pub(crate) fn words(normalized: &[u8]) -> Vec<&[u8]> {
+    tokens(normalized, |c| c == b' ')
+}
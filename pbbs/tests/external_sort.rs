@@ -0,0 +1,155 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+#[path ="../src/common/io.rs"] mod io;
+#[path ="../src/common/external_sort.rs"] mod external_sort;
+#[path ="../src/benchmarks/integer_sort/parallel_radix_sort.rs"]
+mod parallel_radix_sort;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use external_sort::external_sort;
+use parallel_radix_sort::{int_sort, IntSortable, KeyType};
+use parlay::utilities::hash64;
+
+
+fn write_input(dir: &Path, name: &str, vals: &[u32]) -> PathBuf {
+    let p = dir.join(name);
+    let body: Vec<String> = vals.iter().map(u32::to_string).collect();
+    fs::write(&p, format!("sequenceInt\n{}\n", body.join("\n"))).unwrap();
+    p
+}
+
+fn sort_file(inp: &Path, out: &Path, tmp: &Path, mem: usize) -> Result<(), String> {
+    external_sort(
+        inp.to_str().unwrap(),
+        out.to_str().unwrap(),
+        mem,
+        tmp,
+        |l| l.trim().parse::<u32>().ok(),
+        |c: &mut Vec<u32>| c.sort(),
+        |a, b| a < b,
+        u32::to_string
+    )
+}
+
+fn read_output(p: &Path) -> Vec<u32> {
+    fs::read_to_string(p).unwrap().lines().map(|l| l.parse().unwrap()).collect()
+}
+
+#[test]
+fn concurrent_sorts_share_a_tmp_dir() {
+    let dir = std::env::temp_dir().join(format!("pbbs_ext_test_{}", std::process::id()));
+    let tmp = dir.join("runs");
+    fs::create_dir_all(&tmp).unwrap();
+
+    // tiny memory limits make many runs, each with the minimum buffer
+    let inputs: Vec<Vec<u32>> = (0..4)
+        .map(|t| (0..50_000u32).map(|i| i.wrapping_mul(2654435761) ^ t).collect())
+        .collect();
+    std::thread::scope(|s| {
+        for (t, vals) in inputs.iter().enumerate() {
+            let (dir, tmp) = (&dir, &tmp);
+            s.spawn(move || {
+                let inp = write_input(dir, &format!("in_{t}"), vals);
+                let out = dir.join(format!("out_{t}"));
+                sort_file(&inp, &out, tmp, 1 << 10).unwrap();
+                let mut exp = vals.clone();
+                exp.sort();
+                assert_eq!(read_output(&out), exp);
+            });
+        }
+    });
+    assert_eq!(fs::read_dir(&tmp).unwrap().count(), 0);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rejects_malformed_lines() {
+    let dir = std::env::temp_dir().join(format!("pbbs_ext_bad_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let inp = dir.join("in");
+    fs::write(&inp, "sequenceInt\n3\n\n1\n-2\n").unwrap();
+    let r = sort_file(&inp, &dir.join("out"), &dir, 1 << 20);
+    assert_eq!(r, Err("cannot parse line \"-2\"".to_string()));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// sorts the keys the way isort's external mode does
+fn sort_keys<T>(dir: &Path, name: &str, vals: &[T]) -> Vec<T>
+where
+    T: IntSortable + Default + Debug + Display + FromStr + Ord,
+{
+    let inp = dir.join(name);
+    let body: Vec<String> = vals.iter().map(T::to_string).collect();
+    fs::write(&inp, format!("sequenceInt\n{}\n", body.join("\n"))).unwrap();
+    let out = dir.join(format!("{name}.out"));
+    external_sort(
+        inp.to_str().unwrap(),
+        out.to_str().unwrap(),
+        1 << 12,
+        dir,
+        |l| l.trim().parse::<T>().ok(),
+        |c: &mut Vec<T>| { let mut r = vec![]; int_sort(c, 0, &mut r); *c = r; },
+        |a: T, b: T| a.key() < b.key(),
+        T::to_string
+    ).unwrap();
+    fs::read_to_string(&out)
+        .unwrap()
+        .lines()
+        .map(|l| l.parse().ok().unwrap())
+        .collect()
+}
+
+#[test]
+fn sorts_negative_and_wide_keys() {
+    let dir = std::env::temp_dir().join(format!("pbbs_ext_keys_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let n = 20_000;
+
+    let i32s: Vec<i32> = (0..n).map(|i| hash64(i) as i32).collect();
+    let i64s: Vec<i64> = (0..n).map(|i| hash64(i) as i64).collect();
+    let u64s: Vec<u64> = (0..n).map(|i| hash64(i) | 1 << 40).collect();
+    assert_eq!(KeyType::of_range(-1, 7), Some(KeyType::I32));
+    assert_eq!(KeyType::of_range(i64::MIN as i128, 0), Some(KeyType::I64));
+    assert_eq!(KeyType::of_range(0, u64::MAX as i128), Some(KeyType::U64));
+    assert_eq!(KeyType::of_range(-1, u64::MAX as i128), None);
+
+    let mut exp = i32s.clone();
+    exp.sort();
+    assert_eq!(sort_keys(&dir, "i32", &i32s), exp);
+    let mut exp = i64s.clone();
+    exp.sort();
+    assert_eq!(sort_keys(&dir, "i64", &i64s), exp);
+    let mut exp = u64s.clone();
+    exp.sort();
+    assert_eq!(sort_keys(&dir, "u64", &u64s), exp);
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,43 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+#[path ="../src/common/io.rs"] mod io;
+
+use io::{lines, split_lines};
+
+
+#[test]
+fn split_lines_keeps_empty_lines() {
+    for s in ["", "\n", "a", "a\n", "a\n\nb", "\n\na\nbb\n\n", "é\nü\n"] {
+        assert_eq!(split_lines(s), s.split('\n').collect::<Vec<_>>(), "{:?}", s);
+        let non_empty: Vec<_> = s.split('\n').filter(|l| !l.is_empty()).collect();
+        assert_eq!(lines(s), non_empty, "{:?}", s);
+    }
+    let big: String = (0..100_000).map(|i| if i % 7 == 0 { "\n" } else { "x\n" }).collect();
+    assert_eq!(split_lines(&big), big.split('\n').collect::<Vec<_>>());
+}
@@ -0,0 +1,89 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+#[path ="../src/misc.rs"] mod misc;
+#[path ="../src/common/graph.rs"] mod graph;
+#[path ="../src/algorithm/union_find.rs"] mod union_find;
+#[path ="../src/benchmarks/min_span_forest/mod.rs"] mod msf;
+
+use misc::*;
+use graph::{WghEdge, WghEdgeArray};
+use union_find::UnionFind;
+use parlay::utilities::hash64;
+
+
+// a random graph with few distinct weights, so that there are many ties
+fn graph(n: usize, m: usize) -> WghEdgeArray {
+    let es = (0..m as u64)
+        .map(|i| WghEdge::new(
+            (hash64(3 * i) % n as u64) as DefInt,
+            (hash64(3 * i + 1) % n as u64) as DefInt,
+            (hash64(3 * i + 2) % 16) as DefFloat
+        )).collect();
+    WghEdgeArray::new(es, n)
+}
+
+// the weight of a minimum spanning forest by a plain Kruskal
+fn kruskal_weight(g: &WghEdgeArray) -> f64 {
+    let mut es = g.es.clone();
+    es.sort_by(|a, b| a.w.total_cmp(&b.w));
+    let mut uf = UnionFind::new(g.n);
+    es.iter().fold(0.0, |acc, e| {
+        let (u, v) = (uf.find(e.u as DefIntS), uf.find(e.v as DefIntS));
+        if u == v { return acc; }
+        uf.link(u, v);
+        acc + e.w as f64
+    })
+}
+
+fn weight(g: &WghEdgeArray, ids: &[DefInt]) -> f64 {
+    ids.iter().map(|&i| g[i as usize].w as f64).sum()
+}
+
+#[test]
+fn serial_msf_overwrites_dest() {
+    let g = graph(1000, 5000);
+    let mut dest = vec![];
+    msf::serial_msf::minimum_spanning_forest(&g, &mut dest);
+    let first = dest.clone();
+    msf::serial_msf::minimum_spanning_forest(&g, &mut dest);
+    assert_eq!(dest, first);
+    assert_eq!(weight(&g, &dest), kruskal_weight(&g));
+}
+
+#[test]
+fn inc_msf_mod_is_minimum() {
+    for (n, m) in [(100, 1000), (1000, 5000), (10_000, 100_000)] {
+        let g = graph(n, m);
+        // inc_msf_mod relabels the endpoints of the edges it visits
+        let g_copy = g.clone();
+        let mut dest = vec![];
+        msf::inc_msf_mod::minimum_spanning_forest(&g_copy, &mut dest);
+        assert_eq!(weight(&g, &dest), kruskal_weight(&g), "n={n}, m={m}");
+    }
+}
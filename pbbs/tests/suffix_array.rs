@@ -0,0 +1,110 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+#[path ="../src/benchmarks/suffix_array/mod.rs"] mod sa;
+#[path ="../src/misc.rs"] mod misc;
+#[path ="../src/common/io.rs"] mod io;
+#[path ="../src/algorithm/suffix_array.rs"] mod suffix_array;
+
+use misc::*;
+use sa::{dc3, parallel_range};
+use suffix_array::Symbol;
+use io::ints_from_chars;
+use parlay::utilities::hash64;
+
+
+#[cfg(not(feature = "AW_safe"))]
+fn run<T: Symbol>(f: fn(&[T], &mut [DefInt]), s: &[T]) -> Vec<DefInt> {
+    let mut r = vec![0; s.len()];
+    f(s, &mut r);
+    r
+}
+
+#[cfg(feature = "AW_safe")]
+fn run<T: Symbol>(f: fn(&[T], &mut [DefAtomInt]), s: &[T]) -> Vec<DefInt> {
+    let mut r: Vec<DefAtomInt> = (0..s.len()).map(|_| DefAtomInt::default()).collect();
+    f(s, &mut r);
+    r.into_iter().map(|x| x.into_inner()).collect()
+}
+
+fn naive<T: Ord>(s: &[T]) -> Vec<DefInt> {
+    let mut r: Vec<DefInt> = (0..s.len() as DefInt).collect();
+    r.sort_by(|&a, &b| s[a as usize..].cmp(&s[b as usize..]));
+    r
+}
+
+fn check<T: Symbol>(s: &[T]) {
+    let expected = run(parallel_range::suffix_array::<T>, s);
+    assert_eq!(run(dc3::suffix_array::<T>, s), expected, "n = {}", s.len());
+    if s.len() <= 2000 { assert_eq!(expected, naive(s)); }
+}
+
+#[test]
+fn dc3_small() {
+    assert!(run(dc3::suffix_array::<u8>, b"").is_empty());
+    for s in ["a", "ab", "ba", "aa", "banana", "mississippi", "abcabcabc"] {
+        check(s.as_bytes());
+    }
+}
+
+#[test]
+fn dc3_repetitive() {
+    for n in [100, 1000, 100_000] {
+        check(&vec![b'a'; n]);
+        let ab: Vec<u8> = (0..n).map(|i| b"ab"[i % 2]).collect();
+        check(&ab);
+        // a long period, so that the recursion goes deep
+        let p: Vec<u8> = (0..n).map(|i| b"abaabaaab"[i % 9]).collect();
+        check(&p);
+    }
+}
+
+#[test]
+fn dc3_random() {
+    for n in [1000, 100_000] {
+        let s: Vec<u8> = (0..n as u64).map(|i| b'a' + (hash64(i) % 4) as u8).collect();
+        check(&s);
+    }
+}
+
+#[test]
+fn dc3_sequence_int() {
+    // a large alphabet with negative symbols
+    let n = 50_000u64;
+    let mut text = String::from("sequenceInt\n");
+    for i in 0..n {
+        let x = (hash64(i % 1000) % 100_000) as i64 - 50_000;
+        text += &format!("{x}\n");
+    }
+    let ints = ints_from_chars(text.as_bytes()).unwrap().unwrap();
+    assert_eq!(ints.len(), n as usize);
+    check(&ints);
+
+    let small: Vec<i64> = vec![3, -1, 3, -1, 3, 7, -1];
+    check(&small);
+}
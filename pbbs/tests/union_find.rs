@@ -0,0 +1,102 @@
+// ============================================================================
+// This code is part of Rusty-PBBS.
+// ----------------------------------------------------------------------------
+// MIT License
+// 
+// Copyright (c) 2023-present Javad Abdi, Mark C. Jeffrey
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// ============================================================================
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use rayon::prelude::*;
+
+#[path ="../src/misc.rs"] mod misc;
+#[path ="../src/algorithm/union_find.rs"] mod union_find;
+
+use misc::*;
+use union_find::{AtomicUnionFind, FindStrategy, UnionFind};
+use parlay::utilities::hash64;
+
+
+fn edges(n: usize, m: usize) -> Vec<(DefIntS, DefIntS)> {
+    (0..m as u64)
+        .map(|i| (
+            (hash64(2 * i) % n as u64) as DefIntS,
+            (hash64(2 * i + 1) % n as u64) as DefIntS
+        )).collect()
+}
+
+// the root of every vertex after uniting the edges one by one
+fn serial_components(n: usize, es: &[(DefIntS, DefIntS)]) -> Vec<DefIntS> {
+    let mut uf = UnionFind::new(n);
+    for &(u, v) in es {
+        let (ru, rv) = (uf.find(u), uf.find(v));
+        if ru != rv { uf.union_roots(ru, rv); }
+    }
+    (0..n as DefIntS).map(|u| uf.find(u)).collect()
+}
+
+// both labelings induce the same partition of the vertices
+fn same_partition(a: &[DefIntS], b: &[DefIntS]) -> bool {
+    let pairs: HashSet<_> = a.iter().zip(b).collect();
+    let la: HashSet<_> = a.iter().collect();
+    let lb: HashSet<_> = b.iter().collect();
+    pairs.len() == la.len() && pairs.len() == lb.len()
+}
+
+#[test]
+fn unite_matches_serial() {
+    let strategies = [
+        FindStrategy::Compression,
+        FindStrategy::Splitting,
+        FindStrategy::Halving
+    ];
+    for (n, m) in [(1, 1), (100, 50), (100_000, 50_000), (100_000, 300_000)] {
+        let es = edges(n, m);
+        let expected = serial_components(n, &es);
+        let num_components = expected.iter().collect::<HashSet<_>>().len();
+        for strategy in strategies {
+            let uf = AtomicUnionFind::with_strategy(n, strategy);
+            let united = es
+                .par_iter()
+                .filter(|&&(u, v)| uf.unite(u, v))
+                .count();
+            let comps = uf.components();
+            assert!(same_partition(&comps, &expected), "{} n = {}", strategy, n);
+            // every successful unite merges two sets
+            assert_eq!(united, n - num_components);
+            // a component is labeled by its root
+            assert!(comps.iter().all(|&r| comps[r as usize] == r));
+        }
+    }
+}
+
+#[test]
+fn unite_same_set() {
+    let uf = AtomicUnionFind::new(4);
+    assert!(uf.unite(0, 1));
+    assert!(uf.unite(2, 1));
+    assert!(!uf.unite(0, 2));
+    assert!(!uf.unite(3, 3));
+    assert_eq!(uf.find(0), uf.find(2));
+    assert_ne!(uf.find(0), uf.find(3));
+}